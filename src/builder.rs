@@ -0,0 +1,113 @@
+use crate::escape::escape_value;
+use crate::property::{Property, PropertyOwned};
+use crate::vcard::{Name, VCard, VCardOwned};
+use crate::version::Version;
+
+/// Builds a new card one property at a time.
+#[derive(Debug, Clone)]
+pub struct VCardBuilder {
+    version: Version,
+    properties: Vec<PropertyOwned>,
+    generate_full_name: bool,
+}
+
+impl Default for VCardBuilder {
+    fn default() -> Self {
+        VCardBuilder::new(Version::default())
+    }
+}
+
+impl VCardBuilder {
+    pub fn new(version: Version) -> Self {
+        VCardBuilder {
+            version,
+            properties: vec![],
+            generate_full_name: true,
+        }
+    }
+
+    /// Adds a property as it is.
+    pub fn property(mut self, property: PropertyOwned) -> Self {
+        self.properties.push(property);
+        self
+    }
+
+    /// Sets `FN`, escaping `full_name`.
+    pub fn full_name(self, full_name: &str) -> Self {
+        let value = escape_value(full_name).into_owned();
+        self.without("FN").property(Property::new("FN", value))
+    }
+
+    /// Sets `N` from its components, escaping each.
+    pub fn name(self, name: &Name<'_>) -> Self {
+        self.without("N")
+            .property(Property::new("N", name.to_value()))
+    }
+
+    /// Whether [`build`](VCardBuilder::build) adds an `FN` made from `N`
+    /// when none was given, as it does by default. `FN` is required, so
+    /// only turn this off to add one some other way.
+    pub fn generate_full_name(mut self, generate: bool) -> Self {
+        self.generate_full_name = generate;
+        self
+    }
+
+    pub fn build(mut self) -> VCardOwned {
+        let has_full_name = self.properties.iter().any(|p| p.is("FN"));
+        let name = self.properties.iter().find(|p| p.is("N"));
+        if let Some(name) = name.filter(|_| self.generate_full_name && !has_full_name) {
+            let full_name = Name::from_value(&name.raw_value()).given_and_family();
+            if !full_name.is_empty() {
+                let value = escape_value(&full_name).into_owned();
+                self.properties.insert(0, Property::new("FN", value));
+            }
+        }
+
+        VCard::new(self.version, self.properties)
+    }
+
+    fn without(mut self, name: &str) -> Self {
+        self.properties.retain(|p| !p.is(name));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jane() -> Name<'static> {
+        Name {
+            family_name: "Doe".into(),
+            given_name: "Jane".into(),
+            additional_name: "".into(),
+            prefix: "Dr.".into(),
+            suffix: "".into(),
+        }
+    }
+
+    #[test]
+    fn full_name_from_name() {
+        let card = VCardBuilder::new(Version::V40).name(&jane()).build();
+
+        assert_eq!(card.full_name().as_deref(), Some("Jane Doe"));
+        assert_eq!(card.get("N").unwrap().value, vec!["Doe;Jane;;Dr.;"]);
+        assert_eq!(card.properties[0].name, "FN");
+    }
+
+    #[test]
+    fn explicit_full_name_wins() {
+        let card = VCardBuilder::default()
+            .name(&jane())
+            .full_name("Dr. Jane Doe")
+            .build();
+        assert_eq!(card.full_name().as_deref(), Some("Dr. Jane Doe"));
+        assert_eq!(card.get_all("FN").len(), 1);
+
+        let card = VCardBuilder::default()
+            .name(&jane())
+            .generate_full_name(false)
+            .build();
+        assert_eq!(card.full_name(), None);
+    }
+}
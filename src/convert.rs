@@ -0,0 +1,357 @@
+use std::borrow::Cow;
+
+use crate::media::{inline_media_type, is_base64};
+use crate::property::Property;
+use crate::vcard::VCard;
+use crate::version::Version;
+
+/// Properties that 4.0 removed.
+const REMOVED_IN_40: &[&str] = &[
+    "AGENT",
+    "CLASS",
+    "LABEL",
+    "MAILER",
+    "NAME",
+    "PROFILE",
+    "SORT-STRING",
+];
+
+/// Properties that 4.0 introduced.
+const ADDED_IN_40: &[&str] = &[
+    "ANNIVERSARY",
+    "CLIENTPIDMAP",
+    "GENDER",
+    "KIND",
+    "LANG",
+    "MEMBER",
+    "XML",
+];
+
+/// Properties that 3.0 introduced.
+const ADDED_IN_30: &[&str] = &[
+    "CATEGORIES",
+    "CLASS",
+    "NAME",
+    "NICKNAME",
+    "PRODID",
+    "PROFILE",
+    "SORT-STRING",
+    "SOURCE",
+];
+
+/// Properties whose value is binary data, inline or behind a URI.
+pub(crate) const MEDIA: &[&str] = &["KEY", "LOGO", "PHOTO", "SOUND"];
+
+impl<'a> VCard<'a> {
+    /// Rewrites the card in place so it is valid for `version`.
+    ///
+    /// Going to 4.0, `TYPE=PREF` becomes `PREF=1`, `GEO` becomes a `geo:` URI,
+    /// a UTC offset `TZ` is marked `VALUE=utc-offset` and inline `ENCODING=b`
+    /// media becomes a `data:` URI. Going down from 4.0 does the reverse, and
+    /// a `tel:` URI becomes the bare number, with its `ext` written after it
+    /// as ` x1234`. Other `tel:` parameters, such as `isub`, are dropped.
+    ///
+    /// Properties the target version doesn't define are dropped:
+    ///
+    /// - 4.0 drops `AGENT`, `CLASS`, `LABEL`, `MAILER`, `NAME`, `PROFILE` and
+    ///   `SORT-STRING`.
+    /// - 3.0 drops `ANNIVERSARY`, `CLIENTPIDMAP`, `GENDER`, `KIND`, `LANG`,
+    ///   `MEMBER` and `XML`.
+    /// - 2.1 drops everything 3.0 does, plus `CATEGORIES`, `CLASS`, `NAME`,
+    ///   `NICKNAME`, `PRODID`, `PROFILE`, `SORT-STRING` and `SOURCE`.
+    pub fn convert_to(&mut self, version: Version) {
+        let from = self.version();
+        if from == version {
+            return;
+        }
+
+        let unsupported: Vec<&str> = match version {
+            Version::V40 => REMOVED_IN_40.to_vec(),
+            Version::V30 => ADDED_IN_40.to_vec(),
+            Version::V21 => [ADDED_IN_40, ADDED_IN_30].concat(),
+        };
+        self.properties
+            .retain(|p| !unsupported.iter().any(|name| p.is(name)));
+
+        for property in self.properties.iter_mut() {
+            if version == Version::V40 {
+                upgrade(property);
+            } else if from == Version::V40 {
+                downgrade(property);
+            }
+
+            if is_base64(property) {
+                if let Some(i) = find_param(property, "ENCODING") {
+                    property.params[i].1 = match version {
+                        Version::V21 => "BASE64".into(),
+                        _ => "b".into(),
+                    };
+                }
+            }
+        }
+
+        self.version = version;
+    }
+}
+
+fn upgrade(property: &mut Property<'_>) {
+    let mut preferred = false;
+    for (name, value) in property.params.iter_mut() {
+        if name.eq_ignore_ascii_case("TYPE") {
+            let types: Vec<&str> = value
+                .split(',')
+                .filter(|t| {
+                    let pref = t.eq_ignore_ascii_case("PREF");
+                    preferred |= pref;
+                    !pref
+                })
+                .collect();
+            *value = Cow::Owned(types.join(","));
+        }
+    }
+    if preferred {
+        property
+            .params
+            .retain(|(name, value)| !(name.eq_ignore_ascii_case("TYPE") && value.is_empty()));
+        if find_param(property, "PREF").is_none() {
+            property.params.push(("PREF".into(), "1".into()));
+        }
+    }
+
+    if property.is("GEO") {
+        let value = property.raw_value();
+        if let Some((lat, lon)) = value.split_once(';') {
+            property.value = vec![Cow::Owned(format!("geo:{},{}", lat, lon))];
+        }
+    } else if property.is("TZ") {
+        let value = property.raw_value();
+        if let Some(offset) = utc_offset(&value) {
+            let offset = offset.replace(':', "");
+            property.remove_param("VALUE");
+            property.params.push(("VALUE".into(), "utc-offset".into()));
+            property.value = vec![Cow::Owned(offset)];
+        } else {
+            property.remove_param("VALUE");
+        }
+    } else if MEDIA.iter().any(|name| property.is(name)) {
+        upgrade_media(property);
+    }
+}
+
+pub(crate) fn upgrade_media(property: &mut Property<'_>) {
+    if !is_base64(property) {
+        property.remove_param("VALUE");
+        return;
+    }
+
+    let media_type =
+        inline_media_type(property).unwrap_or_else(|| "application/octet-stream".to_string());
+    let data = property.raw_value();
+    let uri = format!("data:{};base64,{}", media_type, data);
+
+    property.remove_param("ENCODING");
+    property.remove_param("TYPE");
+    property.remove_param("VALUE");
+    property.value = vec![Cow::Owned(uri)];
+}
+
+fn downgrade(property: &mut Property<'_>) {
+    if let Some(i) = find_param(property, "PREF") {
+        property.params.remove(i);
+        match find_param(property, "TYPE") {
+            Some(i) => {
+                let types = format!("{},PREF", property.params[i].1);
+                property.params[i].1 = Cow::Owned(types);
+            }
+            None => property.params.push(("TYPE".into(), "PREF".into())),
+        }
+    }
+
+    if property.is("GEO") {
+        let value = property.raw_value();
+        if let Some(uri) = strip_prefix_ignore_case(&value, "geo:") {
+            let coordinates = uri.split(';').next().unwrap_or_default();
+            if let Some((lat, lon)) = coordinates.split_once(',') {
+                property.value = vec![Cow::Owned(format!("{};{}", lat, lon))];
+            }
+        }
+    } else if property.is("TZ") {
+        let value = property.raw_value();
+        if let Some(offset) = utc_offset(&value) {
+            let offset = if offset.contains(':') {
+                offset.to_string()
+            } else {
+                format!("{}:{}", &offset[..3], &offset[3..])
+            };
+            property.remove_param("VALUE");
+            property.value = vec![Cow::Owned(offset)];
+        }
+    } else if property.is("TEL") {
+        let value = property.raw_value();
+        if let Some(number) = strip_prefix_ignore_case(&value, "tel:") {
+            let mut parts = number.split(';');
+            let mut number = parts.next().unwrap_or_default().to_string();
+            if let Some(ext) = parts.find_map(|part| strip_prefix_ignore_case(part, "ext=")) {
+                number = format!("{} x{}", number, ext);
+            }
+            property.remove_param("VALUE");
+            property.value = vec![Cow::Owned(number)];
+        }
+    } else if MEDIA.iter().any(|name| property.is(name)) {
+        downgrade_media(property);
+    }
+}
+
+fn downgrade_media(property: &mut Property<'_>) {
+    let value = property.raw_value();
+    let Some(uri) = strip_prefix_ignore_case(&value, "data:") else {
+        property.params.push(("VALUE".into(), "uri".into()));
+        return;
+    };
+    let Some((media_type, data)) = uri.split_once(";base64,") else {
+        return;
+    };
+
+    let subtype = media_type
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    let data = data.to_string();
+
+    property.remove_param("MEDIATYPE");
+    property.params.push(("ENCODING".into(), "b".into()));
+    property.params.push(("TYPE".into(), Cow::Owned(subtype)));
+    property.value = vec![Cow::Owned(data)];
+}
+
+/// Returns `value` if it is a UTC offset such as `-05:00` or `+0530`.
+fn utc_offset(value: &str) -> Option<&str> {
+    let bytes = value.as_bytes();
+    let digits = match bytes.len() {
+        5 => [1, 2, 3, 4],
+        6 if bytes[3] == b':' => [1, 2, 4, 5],
+        _ => return None,
+    };
+
+    let signed = matches!(bytes[0], b'+' | b'-');
+    if signed && digits.iter().all(|&i| bytes[i].is_ascii_digit()) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+fn find_param(property: &Property<'_>, name: &str) -> Option<usize> {
+    property
+        .params
+        .iter()
+        .position(|(n, _)| n.eq_ignore_ascii_case(name))
+}
+
+fn strip_prefix_ignore_case<'v>(value: &'v str, prefix: &str) -> Option<&'v str> {
+    match value.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&value[prefix.len()..]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_vcard, Property, Version};
+
+    static VCARD_30: &str = "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+TEL;TYPE=CELL,PREF:+1 555 0100\r
+EMAIL;TYPE=INTERNET;TYPE=PREF:jane@example.com\r
+GEO:37.386013;-122.082932\r
+TZ:-05:00\r
+PHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN\r
+MAILER:PigeonMail 2.1\r
+END:VCARD\r
+";
+
+    #[test]
+    fn convert_30_to_40() {
+        let mut card = parse_vcard(VCARD_30).unwrap();
+        card.convert_to(Version::V40);
+
+        assert_eq!(card.version(), Version::V40);
+        assert_eq!(
+            card.get("TEL").unwrap().params,
+            vec![("TYPE".into(), "CELL".into()), ("PREF".into(), "1".into())]
+        );
+        assert_eq!(
+            card.get("EMAIL").unwrap().params,
+            vec![
+                ("TYPE".into(), "INTERNET".into()),
+                ("PREF".into(), "1".into())
+            ]
+        );
+        assert_eq!(
+            card.get("GEO").unwrap().value,
+            vec!["geo:37.386013,-122.082932"]
+        );
+        assert_eq!(
+            card.get("TZ").unwrap(),
+            &Property {
+                group: None,
+                name: "TZ".into(),
+                params: vec![("VALUE".into(), "utc-offset".into())],
+                value: vec!["-0500".into()],
+            }
+        );
+        assert_eq!(
+            card.get("PHOTO").unwrap().value,
+            vec!["data:image/jpeg;base64,MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN"]
+        );
+        assert!(card.get("PHOTO").unwrap().params.is_empty());
+        assert!(card.get("MAILER").is_none());
+    }
+
+    #[test]
+    fn convert_40_back_to_30() {
+        let mut card = parse_vcard(VCARD_30).unwrap();
+        card.convert_to(Version::V40);
+        card.convert_to(Version::V30);
+
+        assert_eq!(card.version(), Version::V30);
+        assert_eq!(
+            card.get("TEL").unwrap().params,
+            vec![("TYPE".into(), "CELL,PREF".into())]
+        );
+        assert_eq!(
+            card.get("GEO").unwrap().value,
+            vec!["37.386013;-122.082932"]
+        );
+        assert_eq!(card.get("TZ").unwrap().value, vec!["-05:00"]);
+        assert_eq!(
+            card.get("PHOTO").unwrap().params,
+            vec![
+                ("ENCODING".into(), "b".into()),
+                ("TYPE".into(), "JPEG".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn tel_extension_and_pref() {
+        let mut card = parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nTEL;VALUE=uri:tel:+1-555-0100;ext=12;isub=3\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+        card.convert_to(Version::V30);
+        assert_eq!(card.get("TEL").unwrap().value, vec!["+1-555-0100 x12"]);
+
+        let mut card = parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nTEL;TYPE=CELL,PREF;PREF=2:+1 555 0100\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+        card.convert_to(Version::V40);
+        assert_eq!(
+            card.get("TEL").unwrap().params,
+            vec![("TYPE".into(), "CELL".into()), ("PREF".into(), "2".into())]
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+
+use crate::error::ParseError;
+use crate::property::Property;
+use crate::vcard::VCard;
+
+impl<'a> VCard<'a> {
+    /// The date of the first `BDAY`, see [`date`](VCard::date).
+    pub fn birthday(&self) -> Option<Result<NaiveDate, ParseError>> {
+        self.get("BDAY").map(date)
+    }
+
+    /// The date of the first `ANNIVERSARY`, see [`date`](VCard::date).
+    pub fn anniversary(&self) -> Option<Result<NaiveDate, ParseError>> {
+        self.get("ANNIVERSARY").map(date)
+    }
+
+    /// The date of the first property called `name`, from a value like
+    /// `1985-04-12`, `19850412` or `19850412T101500Z`. A `CALSCALE` other
+    /// than `gregorian` is an error rather than a date read in the wrong
+    /// calendar, as is a date without a year such as `--0412`.
+    pub fn date(&self, name: &str) -> Option<Result<NaiveDate, ParseError>> {
+        self.get(name).map(date)
+    }
+}
+
+fn date(property: &Property<'_>) -> Result<NaiveDate, ParseError> {
+    if let Some(calscale) = property.param("CALSCALE") {
+        if !calscale.eq_ignore_ascii_case("gregorian") {
+            return Err(ParseError::UnsupportedCalendar {
+                calscale: calscale.to_string(),
+            });
+        }
+    }
+
+    let value = property.raw_value();
+    let day = value.split('T').next().unwrap_or_default().trim();
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(day, "%Y%m%d"))
+        .map_err(|_| ParseError::InvalidDate {
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_vcard;
+
+    #[test]
+    fn gregorian_birthday() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+BDAY;CALSCALE=gregorian:19850412\r
+ANNIVERSARY:2009-08-08T14:30:00-05:00\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.birthday(),
+            Some(Ok(NaiveDate::from_ymd_opt(1985, 4, 12).unwrap()))
+        );
+        assert_eq!(
+            card.get("BDAY").unwrap().param("CALSCALE"),
+            Some("gregorian")
+        );
+        assert_eq!(
+            card.anniversary(),
+            Some(Ok(NaiveDate::from_ymd_opt(2009, 8, 8).unwrap()))
+        );
+        assert_eq!(card.date("DEATHDATE"), None);
+    }
+
+    #[test]
+    fn unsupported_dates() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+BDAY;CALSCALE=x-hebrew:57450722\r
+ANNIVERSARY:--0412\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.birthday(),
+            Some(Err(ParseError::UnsupportedCalendar {
+                calscale: "x-hebrew".to_string()
+            }))
+        );
+        assert_eq!(
+            card.anniversary(),
+            Some(Err(ParseError::InvalidDate {
+                value: "--0412".to_string()
+            }))
+        );
+    }
+}
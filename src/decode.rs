@@ -0,0 +1,44 @@
+/// Decodes a quoted-printable value, as used by 2.1 `ENCODING=QUOTED-PRINTABLE`
+/// properties, into the bytes of its `CHARSET`. Soft line breaks are dropped
+/// and `=` not followed by two hex digits is kept as it is.
+pub fn decode_quoted_printable(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            if bytes[i + 1..].starts_with(b"\r\n") {
+                i += 3;
+                continue;
+            }
+            if let Some(byte) = bytes.get(i + 1..i + 3).and_then(hex_byte) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    decoded
+}
+
+fn hex_byte(digits: &[u8]) -> Option<u8> {
+    let digits = std::str::from_utf8(digits).ok()?;
+    u8::from_str_radix(digits, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_printable() {
+        assert_eq!(decode_quoted_printable("caf=C3=A9"), "café".as_bytes());
+        assert_eq!(decode_quoted_printable("soft=\r\nbreak"), b"softbreak");
+        assert_eq!(decode_quoted_printable("1+1=2 =ZZ ="), b"1+1=2 =ZZ =");
+    }
+}
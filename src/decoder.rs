@@ -0,0 +1,188 @@
+use crate::error::ParseError;
+use crate::parser::{parse_vcards_with, ParseOptions};
+use crate::vcard::{VCard, VCardOwned};
+
+static LF: &[u8] = b"\r\n";
+
+/// Parses cards out of input that arrives in pieces, such as from a socket.
+/// Each card is parsed once, when the chunk holding its `END:VCARD` line
+/// comes in; until then its start is kept in a buffer.
+#[derive(Debug, Clone, Default)]
+pub struct Decoder {
+    options: ParseOptions,
+    buffer: Vec<u8>,
+    /// Where the first line in `buffer` that hasn't ended yet starts.
+    line_start: usize,
+    /// Where the search for the next line end picks up.
+    scanned: usize,
+    /// Where the text after each `END:VCARD` line not yet parsed starts.
+    ends: Vec<usize>,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Decoder::default()
+    }
+
+    pub fn with_options(options: ParseOptions) -> Self {
+        Decoder {
+            options,
+            ..Decoder::default()
+        }
+    }
+
+    /// Adds `chunk` to the input and returns the cards it completed, in
+    /// order. A chunk may end anywhere, even inside a UTF-8 character.
+    ///
+    /// Each card is parsed on its own, so one that fails becomes an error in
+    /// its place without costing the cards around it. Its line numbers are
+    /// counted from the card's `BEGIN:VCARD`.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<Result<VCardOwned, ParseError>> {
+        self.buffer.extend_from_slice(chunk);
+        self.find_complete();
+
+        let mut cards = vec![];
+        let mut start = 0;
+        for &end in &self.ends {
+            let block = std::str::from_utf8(&self.buffer[start..end])
+                .map_err(ParseError::InvalidUtf8)
+                .and_then(|text| parse_vcards_with(text, &self.options));
+            match block {
+                Ok(parsed) => cards.extend(parsed.into_iter().map(VCard::into_owned).map(Ok)),
+                Err(error) => cards.push(Err(error)),
+            }
+            start = end;
+        }
+
+        self.buffer.drain(..start);
+        self.line_start -= start;
+        self.scanned -= start;
+        self.ends.clear();
+        cards
+    }
+
+    /// Ends the input, failing if it stopped in the middle of a card.
+    pub fn finish(self) -> Result<(), ParseError> {
+        let text = std::str::from_utf8(&self.buffer).map_err(ParseError::InvalidUtf8)?;
+        parse_vcards_with(text, &self.options).map(|_| ())
+    }
+
+    /// Records where each `END:VCARD` line ends, looking only at the bytes
+    /// that have come in since the previous push.
+    fn find_complete(&mut self) {
+        let mut from = self.scanned.max(self.line_start);
+        while let Some(len) = self.buffer[from..]
+            .windows(LF.len())
+            .position(|window| window == LF)
+        {
+            let line_end = from + len;
+            let line = &self.buffer[self.line_start..line_end];
+            if line.trim_ascii_end().eq_ignore_ascii_case(b"END:VCARD") {
+                self.ends.push(line_end + LF.len());
+            }
+            self.line_start = line_end + LF.len();
+            from = self.line_start;
+        }
+        // A line end may start in the last byte and finish in the next chunk.
+        self.scanned = self
+            .buffer
+            .len()
+            .saturating_sub(LF.len() - 1)
+            .max(self.line_start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_STRING;
+
+    #[test]
+    fn card_split_across_chunks() {
+        let input = format!("{}{}", TEST_STRING, TEST_STRING.replace("Betty", "Bob"));
+        let bytes = input.as_bytes();
+        let (first, rest) = bytes.split_at(40);
+        let (second, third) = rest.split_at(TEST_STRING.len() + 20 - 40);
+
+        let mut decoder = Decoder::new();
+        assert!(decoder.push(first).is_empty());
+
+        let cards: Vec<_> = decoder
+            .push(second)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].full_name().as_deref(), Some("Hello Betty"));
+
+        let cards: Vec<_> = decoder
+            .push(third)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].full_name().as_deref(), Some("Hello Bob"));
+        assert_eq!(cards[0].get("PHOTO").unwrap().value.len(), 1);
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn unfinished_card() {
+        let mut decoder = Decoder::new();
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:José\r\n";
+        // Between the two bytes of the `é`.
+        let at = text.find('é').unwrap() + 1;
+
+        assert!(decoder.push(&text.as_bytes()[..at]).is_empty());
+        assert!(decoder.push(&text.as_bytes()[at..]).is_empty());
+        assert_eq!(
+            decoder.finish(),
+            Err(ParseError::UnexpectedEnd { properties: 1 })
+        );
+    }
+
+    #[test]
+    fn corrupt_card_is_dropped() {
+        let mut decoder = Decoder::new();
+        let corrupt = b"BEGIN:VCARD\r\nVERSION:3.0\r\nJUSTNAME\r\nEND:VCARD\r\n";
+
+        let cards = decoder.push(corrupt);
+        assert_eq!(cards, vec![Err(ParseError::MalformedProperty { line: 3 })]);
+        let cards = decoder.push(TEST_STRING.as_bytes());
+        assert_eq!(cards.len(), 1);
+        assert_eq!(
+            cards[0].as_ref().unwrap().full_name().as_deref(),
+            Some("Hello Betty")
+        );
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn corrupt_card_in_the_same_chunk() {
+        let mut decoder = Decoder::new();
+        let corrupt = "BEGIN:VCARD\r\nVERSION:3.0\r\nJUSTNAME\r\nEND:VCARD\r\n";
+        let input = format!("{}{}{}", TEST_STRING, corrupt, TEST_STRING);
+
+        let cards = decoder.push(input.as_bytes());
+        assert_eq!(cards.len(), 3);
+        assert_eq!(
+            cards[0].as_ref().unwrap().full_name().as_deref(),
+            Some("Hello Betty")
+        );
+        assert_eq!(cards[1], Err(ParseError::MalformedProperty { line: 3 }));
+        assert!(cards[2].is_ok());
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn one_byte_at_a_time() {
+        let mut decoder = Decoder::new();
+        let mut cards = vec![];
+        for byte in TEST_STRING.as_bytes().chunks(1) {
+            cards.extend(decoder.push(byte).into_iter().map(Result::unwrap));
+        }
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].get("PHOTO").unwrap().value.len(), 1);
+        decoder.finish().unwrap();
+    }
+}
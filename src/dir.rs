@@ -0,0 +1,74 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::ParseError;
+use crate::parser::parse_vcards;
+use crate::vcard::{VCard, VCardOwned};
+
+/// Parses every `.vcf` file directly inside `path`, such as a folder with
+/// one file per contact, in file name order. Subdirectories aren't entered.
+///
+/// The directory is listed up front, which is the only step that can fail
+/// as a whole; each file is then read and parsed as the iterator reaches it,
+/// so a broken file only fails its own entry.
+pub fn parse_dir(
+    path: impl AsRef<Path>,
+) -> io::Result<impl Iterator<Item = (PathBuf, Result<Vec<VCardOwned>, ParseError>)>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        let vcf = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("vcf"));
+        if vcf && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths.into_iter().map(|path| {
+        let cards = parse_file(&path);
+        (path, cards)
+    }))
+}
+
+fn parse_file(path: &Path) -> Result<Vec<VCardOwned>, ParseError> {
+    let bytes = fs::read(path).map_err(|e| ParseError::Io { kind: e.kind() })?;
+    let text = std::str::from_utf8(&bytes).map_err(ParseError::InvalidUtf8)?;
+    let cards = parse_vcards(text)?;
+    Ok(cards.into_iter().map(VCard::into_owned).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_STRING;
+
+    #[test]
+    fn parse_dir() {
+        let dir = std::env::temp_dir().join(format!("vcard-parse-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("betty.vcf"), TEST_STRING).unwrap();
+        fs::write(
+            dir.join("jane.VCF"),
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a card").unwrap();
+
+        let results: Vec<_> = super::parse_dir(&dir).unwrap().collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<String> = results
+            .iter()
+            .map(|(_, cards)| {
+                let cards = cards.as_ref().unwrap();
+                assert_eq!(cards.len(), 1);
+                cards[0].full_name().unwrap()
+            })
+            .collect();
+        assert_eq!(names, vec!["Hello Betty", "Jane Doe"]);
+        assert_eq!(results[1].0, dir.join("jane.VCF"));
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Inline binary data isn't valid base64.
+    Decode(base64::DecodeError),
+    /// A date property's `CALSCALE` is a calendar other than the Gregorian.
+    UnsupportedCalendar { calscale: String },
+    /// A date property's value isn't a complete date.
+    InvalidDate { value: String },
+    /// Decoded text isn't valid UTF-8.
+    InvalidUtf8(std::str::Utf8Error),
+    /// A file couldn't be read.
+    Io { kind: std::io::ErrorKind },
+    /// The input stopped matching the vCard grammar `offset` bytes in.
+    Syntax { offset: usize },
+    /// A content line isn't a valid `name[;params]:value` property. Lines
+    /// are counted from 1 at the start of the input, after unfolding.
+    MalformedProperty { line: usize },
+    /// The content line, counted the same way, has no name or one with
+    /// characters other than letters, digits and `-`.
+    InvalidPropertyName { line: usize },
+    /// The card has no `VERSION` property with a version number this crate
+    /// knows. Lenient parsing assumes 3.0 instead.
+    MissingVersion,
+    /// The input ended before `END:VCARD`, after this many properties had
+    /// been parsed.
+    UnexpectedEnd { properties: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Decode(e) => write!(f, "invalid base64 data: {}", e),
+            ParseError::UnsupportedCalendar { calscale } => {
+                write!(f, "unsupported calendar scale {:?}", calscale)
+            }
+            ParseError::InvalidDate { value } => write!(f, "invalid date {:?}", value),
+            ParseError::InvalidUtf8(e) => write!(f, "invalid UTF-8: {}", e),
+            ParseError::Io { kind } => write!(f, "could not read file: {}", kind),
+            ParseError::Syntax { offset } => write!(f, "invalid vCard syntax at byte {}", offset),
+            ParseError::MalformedProperty { line } => {
+                write!(f, "malformed property on line {}", line)
+            }
+            ParseError::InvalidPropertyName { line } => {
+                write!(f, "invalid property name on line {}", line)
+            }
+            ParseError::MissingVersion => write!(f, "card has no supported VERSION"),
+            ParseError::UnexpectedEnd { properties } => write!(
+                f,
+                "input ended before END:VCARD, after {} properties",
+                properties
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Decode(e) => Some(e),
+            ParseError::InvalidUtf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::{parse_vcard, Media};
+
+    #[test]
+    fn decode_error_source() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nPHOTO;ENCODING=b;TYPE=JPEG:not*base64\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+        let photo: Media = card.photos().remove(0);
+        let error = photo.decode().unwrap().unwrap_err();
+
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "Invalid symbol 42, offset 3.");
+        assert!(parse_vcard("BEGIN:VCARD\r\n")
+            .unwrap_err()
+            .source()
+            .is_none());
+    }
+}
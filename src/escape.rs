@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+
+/// Escapes a text value for use on a content line.
+pub fn escape_value(value: &str) -> Cow<'_, str> {
+    if !value.contains(['\\', ',', ';', '\n', '\r']) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut escaped = String::with_capacity(value.len() + 8);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ',' => escaped.push_str("\\,"),
+            ';' => escaped.push_str("\\;"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    escaped.push_str("\\n");
+                }
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+/// Reverses [`escape_value`], turning `\\`, `\,`, `\;` and `\n` or `\N` back
+/// into the characters they stand for. A lone backslash at the very end of
+/// the value has nothing to escape and is kept as it is.
+pub fn unescape_value(value: &str) -> Cow<'_, str> {
+    if !value.contains('\\') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    Cow::Owned(unescaped)
+}
+
+/// Decodes the RFC 6868 caret escapes in a parameter value: `^n` is a
+/// newline, `^'` a double quote and `^^` a caret. A caret before anything
+/// else is kept as it is.
+pub fn decode_param_value(value: &str) -> Cow<'_, str> {
+    if !value.contains('^') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '^' {
+            decoded.push(c);
+            continue;
+        }
+
+        match chars.peek() {
+            Some('n') | Some('N') => decoded.push('\n'),
+            Some('\'') => decoded.push('"'),
+            Some('^') => decoded.push('^'),
+            _ => {
+                decoded.push('^');
+                continue;
+            }
+        }
+        chars.next();
+    }
+
+    Cow::Owned(decoded)
+}
+
+/// Reverses [`decode_param_value`].
+pub fn encode_param_value(value: &str) -> Cow<'_, str> {
+    if !value.contains(['^', '"', '\n', '\r']) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut encoded = String::with_capacity(value.len() + 8);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '^' => encoded.push_str("^^"),
+            '"' => encoded.push_str("^'"),
+            '\n' => encoded.push_str("^n"),
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    encoded.push_str("^n");
+                }
+            }
+            c => encoded.push(c),
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+/// Decodes the RFC 3986 `%XX` escapes in part of a URI, such as `%20` for a
+/// space. A `%` not followed by two hex digits is kept as it is, and bytes
+/// that don't decode to UTF-8 become U+FFFD.
+pub(crate) fn percent_decode(value: &str) -> Cow<'_, str> {
+    if !value.contains('%') {
+        return Cow::Borrowed(value);
+    }
+
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Properties whose value is a `,`-separated list.
+pub(crate) const LIST_PROPERTIES: &[&str] = &["CATEGORIES", "NICKNAME"];
+
+/// Splits a structured value on the `;` separators that aren't escaped.
+pub(crate) fn split_components(value: &str) -> Vec<&str> {
+    split_unescaped(value, ';')
+}
+
+/// Splits a list value on the `,` separators that aren't escaped.
+pub(crate) fn split_list(value: &str) -> Vec<&str> {
+    split_unescaped(value, ',')
+}
+
+fn split_unescaped(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == separator => {
+                parts.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_round_trip() {
+        let text = "Line one\nsemi; comma, back\\slash";
+        let escaped = escape_value(text);
+
+        assert_eq!(escaped, "Line one\\nsemi\\; comma\\, back\\\\slash");
+        assert_eq!(unescape_value(&escaped), text);
+    }
+
+    #[test]
+    fn trailing_backslash() {
+        assert_eq!(unescape_value("C:\\\\Users\\\\"), "C:\\Users\\");
+        assert_eq!(unescape_value("path\\"), "path\\");
+        assert_eq!(unescape_value("\\\\\\"), "\\\\");
+
+        let card = crate::parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nNOTE:C:\\\\Users\\\\\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            unescape_value(&card.get("NOTE").unwrap().value[0]),
+            "C:\\Users\\"
+        );
+    }
+
+    #[test]
+    fn uppercase_newline_escape() {
+        assert_eq!(
+            unescape_value("line1\\Nline2\\nline3"),
+            "line1\nline2\nline3"
+        );
+
+        let card =
+            crate::parse_vcard("BEGIN:VCARD\r\nVERSION:3.0\r\nNOTE:line1\\Nline2\r\nEND:VCARD\r\n")
+                .unwrap();
+        assert_eq!(
+            unescape_value(&card.get("NOTE").unwrap().raw_value()),
+            "line1\nline2"
+        );
+    }
+
+    #[test]
+    fn percent_decoding() {
+        assert_eq!(percent_decode("Main%20St%2C%204"), "Main St, 4");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%+1"), "%+1");
+        assert_eq!(percent_decode("%zz%C3%A9"), "%zzé");
+        assert!(matches!(percent_decode("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn structured_components() {
+        assert_eq!(
+            split_components("Hello;Betty;;;"),
+            vec!["Hello", "Betty", "", "", ""]
+        );
+        assert_eq!(split_components("a\\;b;c"), vec!["a\\;b", "c"]);
+    }
+
+    #[test]
+    fn caret_escapes() {
+        assert_eq!(decode_param_value("Main St^nSuite 4"), "Main St\nSuite 4");
+        assert_eq!(decode_param_value("^'Bob^'"), "\"Bob\"");
+        assert_eq!(decode_param_value("x^^2"), "x^2");
+        assert_eq!(decode_param_value("a^b^"), "a^b^");
+        assert!(matches!(decode_param_value("plain"), Cow::Borrowed(_)));
+
+        for value in ["Main St\nSuite 4", "\"Bob\"", "x^2", "a^b^"] {
+            assert_eq!(decode_param_value(&encode_param_value(value)), value);
+        }
+        assert_eq!(encode_param_value("a\r\nb^\""), "a^nb^^^'");
+    }
+}
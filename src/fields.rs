@@ -0,0 +1,177 @@
+use crate::escape::unescape_value;
+use crate::property::Property;
+use crate::vcard::VCard;
+
+/// What a [`Field`] holds, for choosing how to render or edit it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldKind {
+    FullName,
+    Nickname,
+    Email,
+    Phone,
+    Address,
+    Organization,
+    Title,
+    Role,
+    Url,
+    Birthday,
+    Note,
+    Categories,
+}
+
+impl FieldKind {
+    fn of(property: &Property<'_>) -> Option<FieldKind> {
+        let kind = match property.canonical_name().as_str() {
+            "FN" => FieldKind::FullName,
+            "NICKNAME" => FieldKind::Nickname,
+            "EMAIL" => FieldKind::Email,
+            "TEL" => FieldKind::Phone,
+            "ADR" => FieldKind::Address,
+            "ORG" => FieldKind::Organization,
+            "TITLE" => FieldKind::Title,
+            "ROLE" => FieldKind::Role,
+            "URL" => FieldKind::Url,
+            "BDAY" => FieldKind::Birthday,
+            "NOTE" => FieldKind::Note,
+            "CATEGORIES" => FieldKind::Categories,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+
+    /// The label used when the property's types don't give a better one.
+    pub fn default_label(&self) -> &'static str {
+        match self {
+            FieldKind::FullName => "Name",
+            FieldKind::Nickname => "Nickname",
+            FieldKind::Email => "Email",
+            FieldKind::Phone => "Phone",
+            FieldKind::Address => "Address",
+            FieldKind::Organization => "Organization",
+            FieldKind::Title => "Title",
+            FieldKind::Role => "Role",
+            FieldKind::Url => "Website",
+            FieldKind::Birthday => "Birthday",
+            FieldKind::Note => "Note",
+            FieldKind::Categories => "Categories",
+        }
+    }
+}
+
+/// One displayable line of a card, see [`VCard::fields`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub label: String,
+    pub kind: FieldKind,
+    pub value: String,
+}
+
+/// Human readable labels for the `TYPE`s worth showing to a user.
+fn type_label(t: &str) -> Option<&'static str> {
+    let label = match t.to_ascii_uppercase().as_str() {
+        "HOME" => "Home",
+        "WORK" => "Work",
+        "CELL" => "Mobile",
+        "FAX" => "Fax",
+        "PAGER" => "Pager",
+        "MAIN" => "Main",
+        "OTHER" => "Other",
+        _ => return None,
+    };
+
+    Some(label)
+}
+
+impl<'a> VCard<'a> {
+    /// Flattens the card into labelled fields, in card order, for rendering
+    /// contact forms. Properties without a [`FieldKind`] are left out.
+    ///
+    /// The label is built from the property's `TYPE`s, e.g. `Work Fax`, and
+    /// falls back to [`FieldKind::default_label`]. Values are unescaped, with
+    /// the non-empty components of structured values joined by `, `.
+    pub fn fields(&self) -> Vec<Field> {
+        self.properties
+            .iter()
+            .filter_map(|property| {
+                let kind = FieldKind::of(property)?;
+                let labels: Vec<&str> = property
+                    .types()
+                    .into_iter()
+                    .filter_map(type_label)
+                    .collect();
+                let label = if labels.is_empty() {
+                    kind.default_label().to_string()
+                } else {
+                    labels.join(" ")
+                };
+                let value = property
+                    .value
+                    .iter()
+                    .map(|component| unescape_value(component))
+                    .filter(|component| !component.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                Some(Field { label, kind, value })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_vcard, TEST_STRING};
+
+    fn field(label: &str, kind: FieldKind, value: &str) -> Field {
+        Field {
+            label: label.to_string(),
+            kind,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn sample_fields() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+
+        assert_eq!(
+            card.fields(),
+            vec![
+                field("Name", FieldKind::FullName, "Hello Betty"),
+                field("Email", FieldKind::Email, "hello.betty@gmail.com"),
+                field("Mobile", FieldKind::Phone, "+91 12342 12332"),
+                field("Mobile", FieldKind::Phone, "+1 (123) 112-123"),
+                field("Role", FieldKind::Role, "Application Engineer"),
+                field("Note", FieldKind::Note, "Gender: Male"),
+                field("Categories", FieldKind::Categories, "myContacts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn structured_field() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+ADR;TYPE=WORK,PARCEL:;;1 Main St\\, Suite 2;Springfield;;;USA\r
+TEL;TYPE=work,fax:+1 555 0100\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.fields(),
+            vec![
+                field(
+                    "Work",
+                    FieldKind::Address,
+                    "1 Main St, Suite 2, Springfield, USA"
+                ),
+                field("Work Fax", FieldKind::Phone, "+1 555 0100"),
+            ]
+        );
+    }
+}
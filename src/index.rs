@@ -0,0 +1,111 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::property::Property;
+use crate::vcard::VCard;
+
+/// Positions of a card's properties by [`canonical_name`], for looking up
+/// many names without scanning the card for each. The card itself stays the
+/// source of truth, borrowed for as long as the index lives.
+///
+/// [`canonical_name`]: Property::canonical_name
+#[derive(Debug, Clone)]
+pub struct PropertyIndex<'c, 'a> {
+    card: &'c VCard<'a>,
+    positions: HashMap<String, Vec<usize>>,
+}
+
+impl<'c, 'a> PropertyIndex<'c, 'a> {
+    /// Every property called `name`, ignoring case, in card order. The same as
+    /// [`VCard::get_all`].
+    pub fn get(&self, name: &str) -> Vec<&'c Property<'a>> {
+        let card = self.card;
+        self.positions
+            .get(&name.to_ascii_uppercase())
+            .map(|positions| positions.iter().map(|&i| &card.properties[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether the card has a property called `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.positions.contains_key(&name.to_ascii_uppercase())
+    }
+}
+
+/// Property counts of a card, e.g. for import statistics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardSummary {
+    /// How many properties there are of each [`canonical_name`], in name
+    /// order.
+    ///
+    /// [`canonical_name`]: Property::canonical_name
+    pub counts: BTreeMap<String, usize>,
+    /// How many properties there are in all, not counting `VERSION`.
+    pub properties: usize,
+    /// The length in bytes of the card as it would be written.
+    pub size: usize,
+}
+
+impl<'a> VCard<'a> {
+    /// Counts the card's properties by name.
+    pub fn summary(&self) -> CardSummary {
+        let index = self.index();
+        let counts: BTreeMap<String, usize> = index
+            .positions
+            .iter()
+            .filter(|(name, _)| name.as_str() != "VERSION")
+            .map(|(name, positions)| (name.clone(), positions.len()))
+            .collect();
+
+        CardSummary {
+            properties: counts.values().sum(),
+            counts,
+            size: self.to_string().len(),
+        }
+    }
+
+    /// Builds a [`PropertyIndex`] over the card.
+    pub fn index(&self) -> PropertyIndex<'_, 'a> {
+        let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, property) in self.properties.iter().enumerate() {
+            positions
+                .entry(property.canonical_name())
+                .or_default()
+                .push(i);
+        }
+
+        PropertyIndex {
+            card: self,
+            positions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_vcard, TEST_STRING};
+
+    #[test]
+    fn index_matches_get_all() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+        let index = card.index();
+
+        for name in ["FN", "tel", "Email", "PHOTO", "ADR", "X-UNKNOWN"] {
+            assert_eq!(index.get(name), card.get_all(name), "{}", name);
+        }
+        assert_eq!(index.get("TEL").len(), 2);
+        assert!(index.contains("categories"));
+        assert!(!index.contains("ADR"));
+    }
+
+    #[test]
+    fn summary() {
+        let summary = parse_vcard(TEST_STRING).unwrap().summary();
+
+        assert_eq!(summary.counts["TEL"], 2);
+        assert_eq!(summary.counts["EMAIL"], 1);
+        assert_eq!(summary.counts.get("ADR"), None);
+        assert_eq!(summary.counts.len(), 8);
+        assert_eq!(summary.properties, 9);
+        assert_eq!(summary.size, TEST_STRING.len());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::vcard::{VCard, VCardOwned};
+
+impl VCard<'_> {
+    /// The card as JSON: its version and its properties with their group,
+    /// name, parameters and raw value components.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("cards always serialize to JSON")
+    }
+
+    /// Reads a card written by [`to_json`](VCard::to_json).
+    pub fn from_json(json: &str) -> Result<VCardOwned, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_vcard, VCard, TEST_STRING};
+
+    #[test]
+    fn json_round_trip() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+        let json = card.to_json();
+
+        assert!(json.starts_with(r#"{"version":"3.0","properties":[{"group":null,"name":"FN""#));
+        assert_eq!(VCard::from_json(&json).unwrap(), card);
+        assert!(VCard::from_json(r#"{"version":"5.0","properties":[]}"#).is_err());
+    }
+}
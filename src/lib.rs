@@ -0,0 +1,72 @@
+//! A parser for vCard (`.vcf`) contact files.
+
+mod builder;
+mod convert;
+#[cfg(feature = "chrono")]
+mod date;
+mod decode;
+mod decoder;
+mod dir;
+mod error;
+mod escape;
+mod fields;
+mod index;
+#[cfg(feature = "serde")]
+mod json;
+mod media;
+#[cfg(feature = "mime")]
+mod mime;
+mod normalize;
+mod parser;
+mod phone;
+mod property;
+mod redact;
+#[cfg(feature = "rfc6715")]
+mod rfc6715;
+mod search;
+mod serialize;
+mod validate;
+mod vcard;
+mod version;
+
+pub use builder::VCardBuilder;
+pub use decode::decode_quoted_printable;
+pub use decoder::Decoder;
+pub use dir::parse_dir;
+pub use error::ParseError;
+pub use escape::{decode_param_value, encode_param_value, escape_value, unescape_value};
+pub use fields::{Field, FieldKind};
+pub use index::{CardSummary, PropertyIndex};
+pub use media::{Media, Photo, Sound};
+#[cfg(feature = "mime")]
+pub use mime::parse_from_mime_part;
+pub use normalize::NormalizeOptions;
+pub use parser::{
+    locate, parse_one, parse_vcard, parse_vcard_with, parse_vcards, parse_vcards_into,
+    parse_vcards_partial, parse_vcards_with, unfold, Location, ParseOptions,
+};
+pub use phone::Phone;
+pub use property::{Property, PropertyOwned};
+#[cfg(feature = "rfc6715")]
+pub use rfc6715::LeveledValue;
+pub use search::search;
+pub use serialize::{serialize_vcards, write_vcards, SerializeOptions};
+pub use validate::Warning;
+pub use vcard::{Name, NameOrder, VCard, VCardOwned};
+pub use version::Version;
+
+#[cfg(test)]
+pub(crate) static TEST_STRING: &str = "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Hello Betty\r
+N:Hello;Betty;;;\r
+EMAIL;TYPE=INTERNET:hello.betty@gmail.com\r
+TEL;TYPE=CELL:+91 12342 12332\r
+TEL;TYPE=CELL:+1 (123) 112-123\r
+ROLE:Application Engineer\r
+NOTE:Gender: Male\r
+PHOTO:https://lh3.googleusercontent.com/contacts/AOq4LdZ2EOkQkPc_KK2CyLAkx1\r
+ 8rcOgp0FYDG3f9_omOYadasd\r
+CATEGORIES:myContacts\r
+END:VCARD\r
+";
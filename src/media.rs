@@ -0,0 +1,224 @@
+use base64::Engine;
+
+use crate::error::ParseError;
+use crate::escape::percent_decode;
+use crate::property::Property;
+use crate::vcard::VCard;
+
+/// The value of a `PHOTO`, `LOGO`, `SOUND` or `KEY`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Media<'a> {
+    /// Base64 data in the card itself, either as a 2.1/3.0 `ENCODING=b`
+    /// value or a `data:` URI. `base64` is as written in the card, so a
+    /// `data:` URI's payload may still be percent-encoded.
+    Inline {
+        media_type: Option<String>,
+        base64: &'a str,
+    },
+    /// A reference to data elsewhere.
+    Uri(&'a str),
+}
+
+pub type Photo<'a> = Media<'a>;
+pub type Sound<'a> = Media<'a>;
+
+impl Media<'_> {
+    pub fn is_inline(&self) -> bool {
+        matches!(self, Media::Inline { .. })
+    }
+
+    /// Decodes inline data, or returns `None` for a [`Media::Uri`].
+    pub fn decode(&self) -> Option<Result<Vec<u8>, ParseError>> {
+        let Media::Inline { base64, .. } = self else {
+            return None;
+        };
+
+        let data: String = percent_decode(base64)
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+        Some(
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(ParseError::Decode),
+        )
+    }
+}
+
+/// Whether the value is inline base64, as `ENCODING=b` in 3.0 or
+/// `ENCODING=BASE64` in 2.1 mark it.
+pub(crate) fn is_base64(property: &Property<'_>) -> bool {
+    property
+        .param("ENCODING")
+        .is_some_and(|e| e.eq_ignore_ascii_case("b") || e.eq_ignore_ascii_case("BASE64"))
+}
+
+/// The media type of inline data. 2.1 and 3.0 only give a subtype like
+/// `JPEG` in `TYPE`, the top-level type follows from the property.
+pub(crate) fn inline_media_type(property: &Property<'_>) -> Option<String> {
+    let subtype = property.param("TYPE")?.to_ascii_lowercase();
+    if subtype.contains('/') {
+        return Some(subtype);
+    }
+
+    let top_level = if property.is("SOUND") {
+        "audio"
+    } else if property.is("KEY") {
+        "application"
+    } else {
+        "image"
+    };
+    Some(format!("{}/{}", top_level, subtype))
+}
+
+fn media<'p>(property: &'p Property<'_>) -> Option<Media<'p>> {
+    let value = property.value.first()?;
+
+    if is_base64(property) {
+        return Some(Media::Inline {
+            media_type: inline_media_type(property),
+            base64: value,
+        });
+    }
+
+    let data_uri = value
+        .get(.."data:".len())
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"));
+    if data_uri {
+        if let Some((media_type, base64)) = value["data:".len()..].split_once(";base64,") {
+            return Some(Media::Inline {
+                media_type: Some(percent_decode(media_type).into_owned()).filter(|t| !t.is_empty()),
+                base64,
+            });
+        }
+    }
+
+    Some(Media::Uri(value))
+}
+
+impl<'a> VCard<'a> {
+    /// Every `PHOTO`, in card order.
+    pub fn photos(&self) -> Vec<Photo<'_>> {
+        self.media("PHOTO")
+    }
+
+    /// Whether the card has a `PHOTO`.
+    pub fn has_photo(&self) -> bool {
+        self.get("PHOTO").is_some()
+    }
+
+    /// Whether the first `PHOTO` is embedded in the card, so there is nothing
+    /// to fetch. Doesn't decode the data.
+    pub fn photo_is_inline(&self) -> bool {
+        self.get("PHOTO")
+            .and_then(media)
+            .is_some_and(|photo| photo.is_inline())
+    }
+
+    /// Every `SOUND`, in card order. Usually a recording of how the
+    /// contact's name is pronounced.
+    pub fn sounds(&self) -> Vec<Sound<'_>> {
+        self.media("SOUND")
+    }
+
+    fn media(&self, name: &str) -> Vec<Media<'_>> {
+        self.get_all(name).into_iter().filter_map(media).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_vcard, TEST_STRING};
+
+    #[test]
+    fn sounds() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+SOUND;VALUE=uri:https://example.com/pronounce/jane.ogg\r
+SOUND;TYPE=WAVE;ENCODING=b:UklGRg==\r
+SOUND:data:audio/ogg;base64,T2dnUw==\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let sounds = card.sounds();
+        assert_eq!(
+            sounds[0],
+            Media::Uri("https://example.com/pronounce/jane.ogg")
+        );
+        assert_eq!(sounds[0].decode(), None);
+        assert_eq!(
+            sounds[1],
+            Media::Inline {
+                media_type: Some("audio/wave".to_string()),
+                base64: "UklGRg==",
+            }
+        );
+        assert_eq!(sounds[1].decode(), Some(Ok(b"RIFF".to_vec())));
+        assert_eq!(sounds[2].decode(), Some(Ok(b"OggS".to_vec())));
+    }
+
+    #[test]
+    fn photo_url() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+        assert!(card.has_photo());
+        assert!(!card.photo_is_inline());
+
+        assert_eq!(
+            card.photos(),
+            vec![Media::Uri(
+                "https://lh3.googleusercontent.com/contacts/AOq4LdZ2EOkQkPc_KK2CyLAkx18rcOgp0FYDG3f9_omOYadasd"
+            )]
+        );
+    }
+
+    #[test]
+    fn inline_photo() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+PHOTO:data:image/png;base64,iVBORw0KGgo=\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+        assert!(card.has_photo());
+        assert!(card.photo_is_inline());
+
+        let mut card = card;
+        card.remove("PHOTO");
+        assert!(!card.has_photo());
+        assert!(!card.photo_is_inline());
+    }
+
+    #[test]
+    fn percent_encoded_data_uri() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+PHOTO:data:image/png;base64,iVBO%2Bw0K\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let photos = card.photos();
+        assert_eq!(
+            photos[0],
+            Media::Inline {
+                media_type: Some("image/png".to_string()),
+                base64: "iVBO%2Bw0K",
+            }
+        );
+        assert_eq!(
+            photos[0].decode(),
+            Some(Ok(vec![0x89, 0x50, 0x4e, 0xfb, 0x0d, 0x0a]))
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use base64::Engine;
+
+use crate::decode::decode_quoted_printable;
+use crate::error::ParseError;
+use crate::parser::parse_vcards;
+use crate::vcard::{VCard, VCardOwned};
+
+/// Parses the cards in a MIME body part, such as a `text/vcard` email
+/// attachment: the headers up to the first blank line are skipped, and the
+/// body is decoded according to its `Content-Transfer-Encoding` before it is
+/// parsed. A part without headers is parsed as it is.
+pub fn parse_from_mime_part(part: &[u8]) -> Result<Vec<VCardOwned>, ParseError> {
+    let (headers, body) = split_headers(part);
+
+    let decoded = match transfer_encoding(headers).as_deref() {
+        Some("base64") => {
+            let data: Vec<u8> = body
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect();
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(ParseError::Decode)?
+        }
+        Some("quoted-printable") => {
+            let body = std::str::from_utf8(body).map_err(ParseError::InvalidUtf8)?;
+            decode_quoted_printable(body)
+        }
+        _ => body.to_vec(),
+    };
+
+    let text = std::str::from_utf8(&decoded).map_err(ParseError::InvalidUtf8)?;
+    let cards = parse_vcards(text)?;
+    Ok(cards.into_iter().map(VCard::into_owned).collect())
+}
+
+/// Splits off the header block, which ends at the first empty line. Input
+/// that starts with `BEGIN:` has no headers.
+fn split_headers(part: &[u8]) -> (&[u8], &[u8]) {
+    if part.starts_with(b"BEGIN:") {
+        return (&[], part);
+    }
+
+    for separator in [&b"\r\n\r\n"[..], b"\n\n"] {
+        if let Some(i) = part.windows(separator.len()).position(|w| w == separator) {
+            return (&part[..i], &part[i + separator.len()..]);
+        }
+    }
+    (&[], part)
+}
+
+/// The lower-cased value of the `Content-Transfer-Encoding` header.
+fn transfer_encoding(headers: &[u8]) -> Option<String> {
+    let headers = String::from_utf8_lossy(headers);
+    headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("Content-Transfer-Encoding")
+            .then(|| value.trim().to_ascii_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_STRING;
+
+    #[test]
+    fn base64_part() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(TEST_STRING);
+        let lines: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(76)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect();
+        let part = format!(
+            "Content-Type: text/vcard; charset=utf-8\r\n\
+             Content-Disposition: attachment; filename=\"betty.vcf\"\r\n\
+             Content-Transfer-Encoding: BASE64\r\n\
+             \r\n\
+             {}\r\n",
+            lines.join("\r\n")
+        );
+
+        let cards = parse_from_mime_part(part.as_bytes()).unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].full_name().as_deref(), Some("Hello Betty"));
+        assert_eq!(parse_from_mime_part(TEST_STRING.as_bytes()).unwrap(), cards);
+    }
+}
@@ -0,0 +1,269 @@
+use std::borrow::Cow;
+
+use crate::escape::{escape_value, split_list, unescape_value};
+use crate::property::Property;
+use crate::vcard::VCard;
+
+/// Properties whose value is escaped text, and so can be re-escaped.
+const TEXT_PROPERTIES: &[&str] = &[
+    "ADR",
+    "CATEGORIES",
+    "FN",
+    "LABEL",
+    "N",
+    "NICKNAME",
+    "NOTE",
+    "ORG",
+    "ROLE",
+    "TITLE",
+];
+
+/// Parameters whose values are case-insensitive keywords.
+const KEYWORD_PARAMS: &[&str] = &["ENCODING", "TYPE", "VALUE"];
+
+/// The number of components in an `ADR`; the last is the country.
+const ADDRESS_COMPONENTS: usize = 7;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Country name for addresses that don't give one, e.g. when importing
+    /// an export from a single region.
+    pub default_country: Option<String>,
+}
+
+impl<'a> VCard<'a> {
+    /// Pads every `ADR` out to its seven components and, if there is a
+    /// [`default_country`](NormalizeOptions::default_country), fills in an
+    /// empty country with it.
+    pub fn normalize_addresses(&mut self, options: &NormalizeOptions) {
+        let country = options
+            .default_country
+            .as_deref()
+            .map(|country| escape_value(country).into_owned());
+
+        for address in self.properties.iter_mut().filter(|p| p.is("ADR")) {
+            if address.value.len() < ADDRESS_COMPONENTS {
+                address.value.resize(ADDRESS_COMPONENTS, Cow::Borrowed(""));
+            }
+
+            let last = &mut address.value[ADDRESS_COMPONENTS - 1];
+            if let Some(country) = country.as_ref().filter(|_| last.trim().is_empty()) {
+                *last = Cow::Owned(country.clone());
+            }
+        }
+    }
+
+    /// Rewrites the card into a canonical form, so that cards written
+    /// differently by different tools compare and serialize the same.
+    ///
+    /// Property, group and parameter names are upper-cased, as are keyword
+    /// parameter values like `TYPE`. Parameters are sorted, text values are
+    /// unescaped and escaped again, and properties are sorted by name and
+    /// then content, keeping the relative order of identical ones.
+    pub fn normalize(&mut self) {
+        for property in self.properties.iter_mut() {
+            normalize_property(property);
+        }
+
+        self.properties
+            .sort_by_cached_key(|p| (p.canonical_name(), p.to_string()));
+    }
+
+    /// Whether the two cards hold the same information, however differently
+    /// written: after [`normalize`](VCard::normalize), with surrounding
+    /// whitespace trimmed from every value component and each property's
+    /// `TYPE`s compared as a set. Sync can use this to tell a real change
+    /// from an export by another tool.
+    pub fn equals_semantic(&self, other: &VCard<'_>) -> bool {
+        self.version == other.version && semantic(self) == semantic(other)
+    }
+}
+
+fn semantic(card: &VCard<'_>) -> Vec<Property<'static>> {
+    let mut card = card.clone().into_owned();
+
+    for property in card.properties.iter_mut() {
+        for value in property.value.iter_mut() {
+            if value.trim().len() != value.len() {
+                *value = Cow::Owned(value.trim().to_string());
+            }
+        }
+
+        let mut types: Vec<String> = property
+            .types()
+            .iter()
+            .map(|t| t.to_ascii_uppercase())
+            .collect();
+        types.sort();
+        types.dedup();
+        property.remove_param("TYPE");
+        if !types.is_empty() {
+            property.add_param("TYPE", types.join(","));
+        }
+    }
+
+    card.normalize();
+    card.properties
+}
+
+fn normalize_property(property: &mut Property<'_>) {
+    property.name = Cow::Owned(property.canonical_name());
+    if let Some(group) = property.group.as_mut() {
+        *group = Cow::Owned(group.to_ascii_uppercase());
+    }
+
+    for (name, value) in property.params.iter_mut() {
+        *name = Cow::Owned(name.to_ascii_uppercase());
+        if KEYWORD_PARAMS.contains(&name.as_ref()) {
+            *value = Cow::Owned(value.to_ascii_uppercase());
+        }
+    }
+    property.params.sort();
+
+    reescape(property);
+}
+
+/// Unescapes and escapes again the value of a text property, so that it
+/// reads the way [`escape_value`] writes it. Other properties are left alone.
+pub(crate) fn reescape(property: &mut Property<'_>) {
+    if !TEXT_PROPERTIES.iter().any(|name| property.is(name)) {
+        return;
+    }
+
+    // Components of structured values can themselves be comma lists.
+    let lists = property.value.len() > 1 || property.is_list();
+    for value in property.value.iter_mut() {
+        let canonical = if lists {
+            split_list(value)
+                .into_iter()
+                .map(|item| escape_value(&unescape_value(item)).into_owned())
+                .collect::<Vec<_>>()
+                .join(",")
+        } else {
+            escape_value(&unescape_value(value)).into_owned()
+        };
+
+        if canonical != *value {
+            *value = Cow::Owned(canonical);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_vcard;
+
+    #[test]
+    fn equivalent_cards_normalize_the_same() {
+        let mut first = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+fn:Jane Doe\r
+EMAIL;type=internet;PREF=1:jane@example.com\r
+TEL;TYPE=CELL:+1 555 0100\r
+TEL;TYPE=HOME:+1 555 0101\r
+NOTE:Lunch\\, then meetings\r
+CATEGORIES:Work,Friends\r
+item1.URL:https://example.com/a,b\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+        let mut second = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+Item1.url:https://example.com/a,b\r
+TEL;type=home:+1 555 0101\r
+NOTE:Lunch, then meetings\r
+Categories:Work,Friends\r
+Email;Pref=1;TYPE=INTERNET:jane@example.com\r
+FN:Jane Doe\r
+tel;type=cell:+1 555 0100\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_ne!(first, second);
+        first.normalize();
+        second.normalize();
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first.to_string(),
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+CATEGORIES:Work,Friends\r
+EMAIL;PREF=1;TYPE=INTERNET:jane@example.com\r
+FN:Jane Doe\r
+NOTE:Lunch\\, then meetings\r
+TEL;TYPE=CELL:+1 555 0100\r
+TEL;TYPE=HOME:+1 555 0101\r
+ITEM1.URL:https://example.com/a,b\r
+END:VCARD\r
+"
+        );
+    }
+
+    #[test]
+    fn semantic_equality() {
+        let first = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+EMAIL;TYPE=HOME;TYPE=internet:jane@example.com\r
+NOTE:Lunch\\, then meetings\r
+TEL:+1 555 0100\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+        let second = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+tel: +1 555 0100 \r
+email;type=INTERNET,HOME:jane@example.com\r
+NOTE:Lunch, then meetings\r
+FN:Jane Doe\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.equals_semantic(&second));
+
+        let mut changed = second.clone();
+        changed.set("TEL", "+1 555 0199");
+        assert!(!first.equals_semantic(&changed));
+    }
+
+    #[test]
+    fn default_country() {
+        let mut card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+ADR;TYPE=HOME:;;1 Main St;Springfield;IL;62701;\r
+ADR;TYPE=WORK:;;2 Rue de Rivoli;Paris;;75001;France\r
+ADR:;;3 High St\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let options = NormalizeOptions {
+            default_country: Some("United States".to_string()),
+        };
+        card.normalize_addresses(&options);
+
+        let countries: Vec<&str> = card
+            .get_all("ADR")
+            .iter()
+            .map(|adr| adr.value[6].as_ref())
+            .collect();
+        assert_eq!(countries, vec!["United States", "France", "United States"]);
+        assert_eq!(card.get_all("ADR")[2].value.len(), 7);
+    }
+}
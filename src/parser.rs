@@ -0,0 +1,1734 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, tag_no_case, take_till, take_till1, take_until, take_while_m_n},
+    character::complete::{digit1, space0},
+    combinator::{eof, map, opt, recognize},
+    multi::{many_till, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+use crate::escape::{decode_param_value, split_components};
+use crate::property::Property;
+use crate::vcard::VCard;
+use crate::version::Version;
+
+type Parameters<'a> = (Vec<(&'a str, &'a str)>, &'a str);
+
+fn parse_vcf_begin(input: &str) -> IResult<&str, ()> {
+    let (input, _) = tuple((tag_no_case("BEGIN:VCARD"), tag(LF)))(input)?;
+    Ok((input, ()))
+}
+
+/// `END:VCARD` and its CRLF. At the very end of the input the CRLF may be
+/// missing, or have become trailing spaces when unfolding joined a line of
+/// them onto this one.
+fn parse_vcf_end(input: &str) -> IResult<&str, ()> {
+    let (input, _) = tuple((tag_no_case("END:VCARD"), space0, alt((tag(LF), eof))))(input)?;
+    Ok((input, ()))
+}
+
+static EQUAL: &str = "=";
+static COLON: &str = ":";
+static SEMI: &str = ";";
+static LF: &str = "\r\n";
+static QUOTE: &str = "\"";
+static QUOTED_PRINTABLE: &str = "QUOTED-PRINTABLE";
+static END: &str = "END";
+
+/// A `name=value` parameter, or a bare 2.1 one such as `WORK` in
+/// `TEL;WORK;VOICE:...` or a stray word after a `;` that should have been a
+/// `,`. Bare values are taken as the `ENCODING` or `TYPE` they stand for.
+fn parse_property_parameter(input: &str) -> IResult<&str, (&str, &str)> {
+    alt((
+        separated_pair(
+            take_till(|c| matches!(c, '=' | ';' | ':' | '\r' | '\n')),
+            tag(EQUAL),
+            parse_parameter_value,
+        ),
+        map(
+            take_till1(|c| matches!(c, ';' | ':' | '\r' | '\n')),
+            |value: &str| (bare_parameter_name(value), value),
+        ),
+    ))(input)
+}
+
+/// The encodings 2.1 allows to be given without `ENCODING=`.
+const ENCODINGS: &[&str] = &["7BIT", "8BIT", "BASE64", "QUOTED-PRINTABLE"];
+
+fn bare_parameter_name(value: &str) -> &'static str {
+    if ENCODINGS.iter().any(|e| e.eq_ignore_ascii_case(value)) {
+        "ENCODING"
+    } else {
+        "TYPE"
+    }
+}
+
+/// A parameter value ends at the first `:` or `;` that isn't quoted or
+/// escaped. Quotes are dropped from the value, backslash escapes are left in
+/// and RFC 6868 caret escapes are decoded by [`parse_property`].
+///
+/// A value that itself contains a `:`, such as a URI, has to be quoted.
+/// Unquoted, the `:` is taken as the one that introduces the property
+/// value, so `X-REF=urn:a:b` is the parameter `urn` and the value `a:b`.
+fn parse_parameter_value(input: &str) -> IResult<&str, &str> {
+    alt((
+        delimited(tag(QUOTE), take_till(|c| c == '"'), tag(QUOTE)),
+        parse_unquoted_parameter_value,
+    ))(input)
+}
+
+fn parse_unquoted_parameter_value(input: &str) -> IResult<&str, &str> {
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ':' | ';' | '\r' | '\n' => return Ok((&input[i..], &input[..i])),
+            _ => {}
+        }
+    }
+
+    Ok(("", input))
+}
+
+fn parse_parameters(input: &str) -> IResult<&str, Parameters<'_>> {
+    many_till(preceded(tag(SEMI), parse_property_parameter), tag(COLON))(input)
+}
+
+/// Longest property or group name accepted, far beyond any in use.
+const MAX_NAME: usize = 256;
+
+type NameParser = fn(&str) -> IResult<&str, &str>;
+
+fn parse_name_token(input: &str) -> IResult<&str, &str> {
+    take_while_m_n(1, MAX_NAME, |c: char| c.is_ascii_alphanumeric() || c == '-')(input)
+}
+
+/// A property name as the spec defines it, letters, digits and `-`, with an
+/// optional group of the same characters before a `.`, such as `item10.TEL`.
+fn parse_property_name(input: &str) -> IResult<&str, &str> {
+    let (rest, name) = recognize(pair(
+        opt(terminated(parse_name_token, tag("."))),
+        parse_name_token,
+    ))(input)?;
+    delimited_name(rest, name)
+}
+
+/// Like [`parse_property_name`], but with a group of any characters other
+/// than a delimiter or `.`, as some vendors write non-ASCII group names.
+fn parse_lenient_property_name(input: &str) -> IResult<&str, &str> {
+    let group = take_till1(|c| matches!(c, '.' | ':' | ';' | '\r' | '\n'));
+    let (rest, name) = recognize(pair(opt(terminated(group, tag("."))), parse_name_token))(input)?;
+    delimited_name(rest, name)
+}
+
+/// Checks that the name is followed by its parameters or value.
+fn delimited_name<'a>(rest: &'a str, name: &'a str) -> IResult<&'a str, &'a str> {
+    if rest.starts_with([':', ';']) {
+        not_end(rest, name)
+    } else {
+        Err(nom::Err::Error(nom::error::Error::new(
+            rest,
+            nom::error::ErrorKind::TakeWhileMN,
+        )))
+    }
+}
+
+/// Anything up to the delimiter that ends a property name.
+fn parse_any_property_name(input: &str) -> IResult<&str, &str> {
+    let (rest, name) = take_till(|c| matches!(c, ':' | ';' | '\r' | '\n'))(input)?;
+    not_end(rest, name)
+}
+
+/// What lenient parsing calls a property whose line has no name.
+const UNKNOWN_NAME: &str = "X-UNKNOWN";
+
+/// The empty name of a line that starts with its parameters or value.
+fn parse_missing_name(input: &str) -> IResult<&str, &str> {
+    if input.starts_with([';', ':']) {
+        Ok((input, ""))
+    } else {
+        Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Tag,
+        )))
+    }
+}
+
+fn not_end<'a>(rest: &'a str, name: &'a str) -> IResult<&'a str, &'a str> {
+    if name.eq_ignore_ascii_case(END) {
+        Err(nom::Err::Error(nom::error::Error::new(
+            rest,
+            nom::error::ErrorKind::Tag,
+        )))
+    } else {
+        Ok((rest, name))
+    }
+}
+
+/// Everything after the colon, spaces included: a value may start with one,
+/// and only a space at the start of a line is folding, which is gone by now.
+fn parse_property_value(input: &str) -> IResult<&str, Vec<&str>> {
+    let (input, v) = take_until(LF)(input)?;
+    Ok((input, vec![v]))
+}
+
+/// A property with a name the spec allows, as [`parse`] reads by default.
+#[cfg(test)]
+fn parse_property(input: &str) -> IResult<&str, Property<'_>> {
+    parse_named_property(input, parse_property_name)
+}
+
+fn parse_named_property(input: &str, names: NameParser) -> IResult<&str, Property<'_>> {
+    let (input, name) = names(input)?;
+    let (input, (params, _)) = parse_parameters(input)?;
+    let (input, value) = parse_property_value(input)?;
+
+    Ok((input, property(name, params, value)))
+}
+
+/// A property on the last line of a truncated input, whose value runs to
+/// the end of it rather than to a CRLF.
+fn parse_final_property(input: &str, names: NameParser) -> IResult<&str, Property<'_>> {
+    let (input, name) = names(input)?;
+    let (input, (params, _)) = parse_parameters(input)?;
+
+    Ok(("", property(name, params, vec![input])))
+}
+
+fn property<'a>(
+    name: &'a str,
+    params: Vec<(&'a str, &'a str)>,
+    value: Vec<&'a str>,
+) -> Property<'a> {
+    let (group, name) = match name.split_once('.') {
+        Some((group, name)) => (Some(group), name),
+        None => (None, name),
+    };
+
+    Property {
+        name: name.into(),
+        params: params
+            .into_iter()
+            .map(|(k, v)| (k.into(), decode_param_value(v)))
+            .collect(),
+        value: value.into_iter().map(Into::into).collect(),
+        group: group.map(Into::into),
+    }
+}
+
+fn parse_properties(input: &str, names: NameParser) -> IResult<&str, Vec<Property<'_>>> {
+    separated_list1(tag(LF), |input| parse_named_property(input, names))(input)
+}
+
+fn parse_version(input: &str) -> IResult<&str, Version> {
+    let (rest, (_, _, _, _, number, _, _)) = tuple((
+        tag_no_case("VERSION"),
+        space0,
+        tag(COLON),
+        space0,
+        recognize(tuple((digit1, tag("."), digit1))),
+        space0,
+        tag(LF),
+    ))(input)?;
+
+    match Version::from_number(number) {
+        Some(version) => Ok((rest, version)),
+        None => Err(nom::Err::Error(nom::error::Error::new(
+            number,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+/// How far parsing may stray from the spec. With the `strict` feature,
+/// `lenient`, `join_base64_lines`, `any_property_name` and `aliases` have no
+/// effect and every card is read exactly as the spec defines it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Skip content lines that can't be parsed, and blank lines between
+    /// properties, instead of failing. Group names may then hold characters
+    /// other than letters, digits and `-`. A card cut off before `END:VCARD`
+    /// keeps what it has, which [`VCard::validate`] reports.
+    pub lenient: bool,
+    /// Trim leading and trailing ASCII whitespace from every value component.
+    pub trim_values: bool,
+    /// Treat lines that look like base64 after an `ENCODING=b` or
+    /// `ENCODING=BASE64` value as part of it, for exporters that wrap the
+    /// data without folding it.
+    pub join_base64_lines: bool,
+    /// Accept property names with characters the spec doesn't allow, such
+    /// as spaces or `_`, as long as they don't contain a delimiter.
+    pub any_property_name: bool,
+    /// Nonstandard property names, ignoring case, and what to read them as:
+    /// a name, optionally with parameters to add, so `MOBILE` can map to
+    /// `TEL;TYPE=CELL`. Of names that only differ in case, the one that
+    /// sorts first is used, whatever order the map holds them in.
+    pub aliases: HashMap<String, String>,
+}
+
+/// Whether a leniency option set to `enabled` applies, which it never does
+/// with the `strict` feature. The tolerant code is still compiled then, but
+/// never runs.
+fn tolerate(enabled: bool) -> bool {
+    enabled && !cfg!(feature = "strict")
+}
+
+/// Parses the card at the start of `rest`, a suffix of `input` that error
+/// positions are reported against.
+fn parse<'a>(
+    input: &'a str,
+    rest: &'a str,
+    options: &ParseOptions,
+) -> Result<(&'a str, VCard<'a>), ParseError> {
+    let syntax = |rest: &str| ParseError::Syntax {
+        offset: input.len() - rest.len(),
+    };
+
+    let start = rest;
+    let (rest, _) = parse_vcf_begin(rest).map_err(|_| syntax(rest))?;
+    // VERSION should come straight after BEGIN. When it doesn't, it is
+    // parsed as any other property and looked for once the card is complete.
+    let (mut rest, version) = match parse_version(rest) {
+        Ok((rest, version)) => (rest, Some(version)),
+        Err(_) if !tolerate(options.lenient) && !rest.contains(LF) => {
+            return Err(ParseError::UnexpectedEnd { properties: 0 })
+        }
+        Err(_) => (rest, None),
+    };
+    let mut properties = vec![];
+    let names: NameParser = if tolerate(options.any_property_name) {
+        parse_any_property_name
+    } else if tolerate(options.lenient) {
+        parse_lenient_property_name
+    } else {
+        parse_property_name
+    };
+
+    let prepare = |mut property: Property<'a>| {
+        // Renamed first, so that an alias for a structured property is split.
+        if tolerate(!options.aliases.is_empty()) {
+            let alias = options
+                .aliases
+                .iter()
+                .filter(|(name, _)| property.is(name))
+                .min_by_key(|(name, _)| *name);
+            if let Some((_, alias)) = alias {
+                apply_alias(&mut property, alias);
+            }
+        }
+        let mut property = split_structured(property);
+        if options.trim_values {
+            trim_values(&mut property);
+        }
+        property
+    };
+    let finish = |mut properties: Vec<Property<'a>>, rest: &'a str| {
+        let version = match version.or_else(|| take_version(&mut properties)) {
+            Some(version) => version,
+            None if tolerate(options.lenient) => Version::default(),
+            None => return Err(ParseError::MissingVersion),
+        };
+        let mut card = VCard::new(version, properties);
+        card.raw = Some(Cow::Borrowed(&start[..start.len() - rest.len()]));
+        Ok((rest, card))
+    };
+
+    loop {
+        if let Ok((rest, _)) = parse_vcf_end(rest) {
+            return finish(properties, rest);
+        }
+
+        // Some exporters put an empty line between properties.
+        if tolerate(options.lenient) {
+            if let Some(tail) = rest.strip_prefix(LF) {
+                rest = tail;
+                continue;
+            }
+        }
+
+        if let Ok((tail, parsed)) = parse_properties(rest, names) {
+            properties.extend(parsed.into_iter().map(prepare));
+            rest = match tag::<_, _, nom::error::Error<&str>>(LF)(tail) {
+                Ok((tail, _)) => tail,
+                Err(_) => return Err(syntax(tail)),
+            };
+            continue;
+        }
+
+        let Some(end) = rest.find(LF) else {
+            if !tolerate(options.lenient) {
+                return Err(ParseError::UnexpectedEnd {
+                    properties: properties.len(),
+                });
+            }
+            // The input was cut short: keep the card as far as it goes,
+            // which `VCard::validate` reports.
+            if let Ok((_, property)) = parse_final_property(rest, names) {
+                properties.push(prepare(property));
+            }
+            return finish(properties, "");
+        };
+        if !tolerate(options.lenient) {
+            let offset = input.len() - rest.len();
+            let line = input[..offset].matches(LF).count() + 1;
+            return Err(match parse_named_property(rest, parse_any_property_name) {
+                Ok(_) => ParseError::InvalidPropertyName { line },
+                Err(_) => ParseError::MalformedProperty { line },
+            });
+        }
+        // A line that lost its name, such as `;TYPE=CELL:+1 555 0100`, still
+        // has a value worth keeping.
+        if let Ok((_, property)) = parse_named_property(rest, parse_missing_name) {
+            properties.push(prepare(Property {
+                name: UNKNOWN_NAME.into(),
+                ..property
+            }));
+        }
+        rest = &rest[end + LF.len()..];
+    }
+}
+
+/// Renames the property to the name in `alias`, and adds the parameters
+/// that follow it, given as on a content line.
+fn apply_alias(property: &mut Property<'_>, alias: &str) {
+    let mut parts = alias.split(';');
+    property.name = Cow::Owned(parts.next().unwrap_or_default().to_string());
+
+    for param in parts.filter(|param| !param.is_empty()) {
+        let (name, value) = param
+            .split_once('=')
+            .unwrap_or((bare_parameter_name(param), param));
+        property.add_param(name.to_string(), value.to_string());
+    }
+}
+
+/// Removes the first `VERSION` property with a known number, returning it.
+fn take_version(properties: &mut Vec<Property<'_>>) -> Option<Version> {
+    let (i, version) = properties.iter().enumerate().find_map(|(i, p)| {
+        let number = p.value.first().filter(|_| p.is("VERSION"))?;
+        Version::from_number(number.trim()).map(|version| (i, version))
+    })?;
+
+    properties.remove(i);
+    Some(version)
+}
+
+/// Properties whose value is a `;`-separated list of components.
+const STRUCTURED: &[&str] = &["ADR", "CLIENTPIDMAP", "GENDER", "GEO", "N", "ORG"];
+
+/// URI schemes that may appear as the value of a structured property, whose
+/// own `;` parameters must not be taken for component separators.
+const URI_SCHEMES: &[&str] = &["data:", "geo:", "http:", "https:", "tel:", "urn:"];
+
+/// Properties whose value may be a URI without `VALUE=uri` saying so.
+const URI_VALUED: &[&str] = &[
+    "CALADRURI",
+    "CALURI",
+    "FBURL",
+    "GEO",
+    "IMPP",
+    "KEY",
+    "LOGO",
+    "MEMBER",
+    "PHOTO",
+    "RELATED",
+    "SOUND",
+    "SOURCE",
+    "TEL",
+    "UID",
+    "URL",
+];
+
+/// Whether the property's value is split into components. Vendor `X-`
+/// extensions such as `X-ANDROID-CUSTOM` often are structured, and since
+/// their layout isn't known they are always split.
+fn is_structured(property: &Property<'_>) -> bool {
+    let extension = property
+        .name
+        .get(..2)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("X-"));
+    extension || STRUCTURED.iter().any(|name| property.is(name))
+}
+
+fn split_structured(mut property: Property<'_>) -> Property<'_> {
+    if !is_structured(&property) || is_uri(&property) {
+        return property;
+    }
+
+    property.value = match std::mem::take(&mut property.value).as_slice() {
+        [Cow::Borrowed(value)] => split_components(value)
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect(),
+        values => values
+            .iter()
+            .flat_map(|value| split_components(value))
+            .map(|component| Cow::Owned(component.to_string()))
+            .collect(),
+    };
+
+    property
+}
+
+fn trim_values(property: &mut Property<'_>) {
+    let is_space = |c: char| c.is_ascii_whitespace();
+
+    for value in property.value.iter_mut() {
+        match value {
+            Cow::Borrowed(value) => *value = value.trim_matches(is_space),
+            Cow::Owned(value) => {
+                let trimmed = value.trim_matches(is_space);
+                if trimmed.len() != value.len() {
+                    *value = trimmed.to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Whether the value is a URI: marked `VALUE=uri`, or starting with a known
+/// scheme in a property that may hold one. Vendor `X-` extensions count as
+/// such, since their value type isn't known.
+fn is_uri(property: &Property<'_>) -> bool {
+    let value_uri = property
+        .params
+        .iter()
+        .any(|(k, v)| k.eq_ignore_ascii_case("VALUE") && v.eq_ignore_ascii_case("uri"));
+    if value_uri {
+        return true;
+    }
+
+    let uri_valued = property
+        .name
+        .get(..2)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("X-"))
+        || URI_VALUED.iter().any(|name| property.is(name));
+    let value = property.value.first().map_or("", |v| v.as_ref());
+    let has_scheme = URI_SCHEMES.iter().any(|scheme| {
+        value
+            .get(..scheme.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(scheme))
+    });
+
+    uri_valued && has_scheme
+}
+
+fn is_folded(input: &str) -> bool {
+    input.contains("\r\n ") || input.contains("\r\n\t")
+}
+
+/// Undoes folding, and before that the soft line breaks of 2.1
+/// quoted-printable values, borrowing `input` when neither is present.
+fn unfolded<'a>(input: &'a str, options: &ParseOptions) -> Cow<'a, str> {
+    let joined = join_soft_breaks(input);
+    let unfolded = if is_folded(&joined) {
+        let mut text = joined.into_owned();
+        unfold(&mut text);
+        Cow::Owned(text)
+    } else {
+        joined
+    };
+
+    match unfolded {
+        Cow::Borrowed(text) if tolerate(options.join_base64_lines) => join_base64_lines(text),
+        Cow::Owned(text) if tolerate(options.join_base64_lines) => {
+            Cow::Owned(join_base64_lines(&text).into_owned())
+        }
+        unfolded => unfolded,
+    }
+}
+
+/// Appends lines made up only of base64 characters to the base64 value
+/// before them. Runs after unfolding, so correctly folded data is already
+/// on one line.
+fn join_base64_lines(input: &str) -> Cow<'_, str> {
+    let mut lines = input.split_inclusive(LF).peekable();
+    let mut joined = String::with_capacity(input.len());
+    let mut changed = false;
+
+    while let Some(line) = lines.next() {
+        if !is_base64_line(line) {
+            joined.push_str(line);
+            continue;
+        }
+
+        let mut line = line;
+        while let Some(next) = lines.peek().filter(|next| looks_like_base64(next)) {
+            joined.push_str(line.strip_suffix(LF).unwrap_or(line));
+            line = next;
+            lines.next();
+            changed = true;
+        }
+        joined.push_str(line);
+    }
+
+    if changed {
+        Cow::Owned(joined)
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Whether the content line carries `ENCODING=b`, `ENCODING=BASE64` or the
+/// bare 2.1 `BASE64` parameter.
+fn is_base64_line(line: &str) -> bool {
+    let head = line.split(COLON).next().unwrap_or_default();
+    head.split(SEMI).skip(1).any(|param| {
+        let value = param.rsplit(EQUAL).next().unwrap_or_default();
+        let encoding = param.len() == value.len()
+            || param[..param.len() - value.len() - 1].eq_ignore_ascii_case("ENCODING");
+        encoding && (value.eq_ignore_ascii_case("b") || value.eq_ignore_ascii_case("BASE64"))
+    })
+}
+
+fn looks_like_base64(line: &str) -> bool {
+    let data = line.strip_suffix(LF).unwrap_or(line);
+    !data.is_empty()
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+}
+
+/// Quoted-printable values continue onto the next physical line after a `=`
+/// soft line break, without the leading space that folding would need. The
+/// `=` and CRLF are dropped so the value reads as one line. A `=` before an
+/// `END:` line is kept, since the card ends there.
+fn join_soft_breaks(input: &str) -> Cow<'_, str> {
+    if !contains_ignore_case(input, QUOTED_PRINTABLE) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut lines = input.split_inclusive(LF).peekable();
+    let mut joined = String::with_capacity(input.len());
+    let mut changed = false;
+    let mut continued = false;
+    let mut quoted_printable = false;
+
+    while let Some(line) = lines.next() {
+        if !continued {
+            let head = line.split(COLON).next().unwrap_or_default();
+            quoted_printable = contains_ignore_case(head, QUOTED_PRINTABLE);
+        }
+
+        let ends_card = lines.peek().is_none_or(|next| {
+            next.get(.."END:".len())
+                .is_some_and(|head| head.eq_ignore_ascii_case("END:"))
+        });
+        match line.strip_suffix("=\r\n") {
+            Some(content) if quoted_printable && !ends_card => {
+                joined.push_str(content);
+                continued = true;
+                changed = true;
+            }
+            _ => {
+                joined.push_str(line);
+                continued = false;
+            }
+        }
+    }
+
+    if changed {
+        Cow::Owned(joined)
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Looks for an ASCII `needle` without making an upper case copy of the
+/// whole `haystack`.
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .any(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Parses a single vCard. Folded lines are unfolded first; the card borrows
+/// from `input` unless that was necessary.
+pub fn parse_vcard(input: &str) -> Result<VCard<'_>, ParseError> {
+    parse_vcard_with(input, &ParseOptions::default())
+}
+
+/// Like [`parse_vcard`], with control over how forgiving the parser is.
+pub fn parse_vcard_with<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> Result<VCard<'a>, ParseError> {
+    match unfolded(input, options) {
+        Cow::Borrowed(input) => parse(input, input, options).map(|(_, card)| card),
+        Cow::Owned(text) => parse(&text, &text, options).map(|(_, card)| card.into_owned()),
+    }
+}
+
+/// Parses the first card in `input`, returning it with the text after its
+/// `END:VCARD` so that the caller can carry on from there.
+pub fn parse_one(input: &str) -> Result<(VCard<'_>, &str), ParseError> {
+    let options = ParseOptions::default();
+    let text = match unfolded(input, &options) {
+        Cow::Borrowed(text) => return parse(text, text, &options).map(|(tail, card)| (card, tail)),
+        Cow::Owned(text) => text,
+    };
+    let (tail, card) = parse(&text, &text, &options)?;
+
+    // The card parsed once unfolded, but its end has to be found in `input`.
+    // Folding never splits the `END:VCARD` line, so the card ends after as
+    // many of them in `input` as it took in the unfolded text. One followed
+    // by a folded line isn't one once unfolded, so it doesn't count.
+    let is_end = |line: &str| line.trim_end().eq_ignore_ascii_case("END:VCARD");
+    let mut ends = text[..text.len() - tail.len()]
+        .split_inclusive(LF)
+        .filter(|line| is_end(line))
+        .count();
+    let mut end = 0;
+    let mut lines = input.split_inclusive(LF).peekable();
+    while let Some(line) = lines.next() {
+        end += line.len();
+        let folded = lines
+            .peek()
+            .is_some_and(|next| next.starts_with([' ', '\t']));
+        if is_end(line) && !folded {
+            ends -= 1;
+            if ends == 0 {
+                break;
+            }
+        }
+    }
+    Ok((card.into_owned(), &input[end..]))
+}
+
+/// Parses a card that owns its text, for when `input` doesn't outlive it.
+impl std::str::FromStr for VCard<'static> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_vcard(input).map(VCard::into_owned)
+    }
+}
+
+/// Parses a card that borrows from `input` where it can, as [`parse_vcard`]
+/// does, so the card can't outlive the text it was parsed from.
+impl<'a> TryFrom<&'a str> for VCard<'a> {
+    type Error = ParseError;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        parse_vcard(input)
+    }
+}
+
+/// Parses every vCard in `input`, e.g. a whole `.vcf` export.
+pub fn parse_vcards(input: &str) -> Result<Vec<VCard<'_>>, ParseError> {
+    parse_vcards_with(input, &ParseOptions::default())
+}
+
+/// Like [`parse_vcards`]. When lenient, anything between cards that isn't a
+/// `BEGIN:VCARD`, such as an iCalendar `VEVENT` block, is skipped.
+pub fn parse_vcards_with<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> Result<Vec<VCard<'a>>, ParseError> {
+    let mut cards = vec![];
+    parse_into(input, options, &mut cards)?;
+    Ok(cards)
+}
+
+/// Like [`parse_vcards`], but keeps the cards before the first one that
+/// fails, returning them along with its error. A final card cut short
+/// before `END:VCARD` costs only itself.
+pub fn parse_vcards_partial(input: &str) -> (Vec<VCard<'_>>, Option<ParseError>) {
+    let options = ParseOptions::default();
+    let mut cards = vec![];
+
+    let result = match unfolded(input, &options) {
+        Cow::Borrowed(input) => parse_each(input, &options, |card| cards.push(card)),
+        Cow::Owned(text) => parse_each(&text, &options, |card| cards.push(card.into_owned())),
+    };
+    (cards, result.err())
+}
+
+/// Like [`parse_vcards`], but appends the cards to `cards` so that one
+/// allocation can be reused across many inputs. On error `cards` is left as
+/// it was.
+pub fn parse_vcards_into<'a>(input: &'a str, cards: &mut Vec<VCard<'a>>) -> Result<(), ParseError> {
+    parse_into(input, &ParseOptions::default(), cards)
+}
+
+fn parse_into<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+    cards: &mut Vec<VCard<'a>>,
+) -> Result<(), ParseError> {
+    let len = cards.len();
+
+    let result = match unfolded(input, options) {
+        Cow::Borrowed(input) => parse_each(input, options, |card| cards.push(card)),
+        Cow::Owned(text) => parse_each(&text, options, |card| cards.push(card.into_owned())),
+    };
+
+    if result.is_err() {
+        cards.truncate(len);
+    }
+    result
+}
+
+fn parse_each<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+    mut f: impl FnMut(VCard<'a>),
+) -> Result<(), ParseError> {
+    let mut rest = skip_to_card(input, options);
+
+    while !rest.is_empty() {
+        let (tail, card) = parse(input, rest, options)?;
+        f(card);
+        rest = skip_to_card(tail, options);
+    }
+
+    Ok(())
+}
+
+/// Skips the blank lines before the next card, and when lenient, every
+/// line that doesn't start one. Nothing but whitespace after the last card,
+/// such as a bare `\n` or trailing spaces, ends the input.
+fn skip_to_card<'a>(mut rest: &'a str, options: &ParseOptions) -> &'a str {
+    loop {
+        if rest.trim_start().is_empty() {
+            return "";
+        }
+        if let Some(tail) = rest.strip_prefix(LF) {
+            rest = tail;
+        } else if rest.is_empty() || !tolerate(options.lenient) || parse_vcf_begin(rest).is_ok() {
+            return rest;
+        } else {
+            rest = match rest.find(LF) {
+                Some(end) => &rest[end + LF.len()..],
+                None => "",
+            };
+        }
+    }
+}
+
+/// Where in the content lines of a card a byte offset falls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location<'a> {
+    /// The content line, counted from 1 after unfolding as in
+    /// [`ParseError::MalformedProperty`].
+    pub line: usize,
+    /// The unfolded text from the offset to the end of the input.
+    pub tail: Cow<'a, str>,
+}
+
+/// Finds the content line holding `offset` in the unfolded `input`, such as
+/// the one in [`ParseError::Syntax`], so that an editor can point at it.
+/// Returns `None` if the offset is past the end or inside a character.
+pub fn locate(input: &str, offset: usize) -> Option<Location<'_>> {
+    let text = unfolded(input, &ParseOptions::default());
+    if !text.is_char_boundary(offset) {
+        return None;
+    }
+
+    let line = text[..offset].matches(LF).count() + 1;
+    let tail = match text {
+        Cow::Borrowed(text) => Cow::Borrowed(&text[offset..]),
+        Cow::Owned(text) => Cow::Owned(text[offset..].to_string()),
+    };
+    Some(Location { line, tail })
+}
+
+/// Joins folded content lines back together in place by removing every
+/// CRLF that is followed by a space or tab, along with that one character.
+/// Any further whitespace is part of the value.
+pub fn unfold(input: &mut String) {
+    if !is_folded(input) {
+        return;
+    }
+
+    let mut unfolded = String::with_capacity(input.len());
+    let mut rest = input.as_str();
+    while let Some(i) = rest.find(LF) {
+        let next = &rest[i + LF.len()..];
+        if next.starts_with([' ', '\t']) {
+            unfolded.push_str(&rest[..i]);
+            rest = &next[1..];
+        } else {
+            unfolded.push_str(&rest[..i + LF.len()]);
+            rest = next;
+        }
+    }
+    unfolded.push_str(rest);
+
+    *input = unfolded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_quoted_printable, TEST_STRING};
+
+    #[test]
+    fn property_parameter() {
+        assert_eq!(
+            parse_property_parameter("hello=test;"),
+            Ok(((";"), ("hello", "test"))),
+        );
+        assert_eq!(
+            parse_parameters(";hello=test:"),
+            Ok(("", (vec![("hello", "test")], ":"))),
+        );
+        assert_eq!(
+            parse_parameters(";hello=test;test=me:"),
+            Ok(("", (vec![("hello", "test"), ("test", "me")], ":"))),
+        );
+        assert_eq!(parse_parameters(":"), Ok(("", (vec![], ":"))),);
+    }
+
+    #[test]
+    fn quoted_and_escaped_parameter() {
+        assert_eq!(
+            parse_property("ADR;LABEL=\"a;b:c\":;;St;;;;\r\n"),
+            Ok((
+                "\r\n",
+                Property {
+                    group: None,
+                    name: "ADR".into(),
+                    params: vec![("LABEL".into(), "a;b:c".into())],
+                    value: vec![";;St;;;;".into()]
+                }
+            )),
+        );
+        assert_eq!(
+            parse_parameters(";X-NOTE=a\\;b\\:c;TYPE=home:"),
+            Ok(("", (vec![("X-NOTE", "a\\;b\\:c"), ("TYPE", "home")], ":"))),
+        );
+    }
+
+    #[test]
+    fn property_name() {
+        assert_eq!(parse_property_name("check;test"), Ok(((";test"), "check")),);
+        assert_eq!(parse_property_name("check:test"), Ok(((":test"), "check")),);
+    }
+
+    #[test]
+    fn property_value() {
+        assert_eq!(parse_property_value("test\r\n"), Ok(("\r\n", vec!["test"])),);
+        assert_eq!(
+            parse_property_value("hello,test\r\n"),
+            Ok(("\r\n", vec!["hello,test"])),
+        );
+        assert_eq!(
+            parse_property_value("al  hello,test\r\n"),
+            Ok(("\r\n", vec!["al  hello,test"])),
+        );
+    }
+
+    #[test]
+    fn property() {
+        assert_eq!(
+            parse_properties(
+                "FN:Hello Betty\r\nN:Hello;Betty;;;\r\n",
+                parse_property_name
+            ),
+            Ok((
+                "\r\n",
+                vec![
+                    Property {
+                        group: None,
+                        name: "FN".into(),
+                        params: vec![],
+                        value: vec!["Hello Betty".into()]
+                    },
+                    Property {
+                        group: None,
+                        name: "N".into(),
+                        params: vec![],
+                        value: vec!["Hello;Betty;;;".into()]
+                    }
+                ]
+            ))
+        );
+        assert_eq!(
+            parse_property("fn:test\r\n"),
+            Ok((
+                "\r\n",
+                Property {
+                    group: None,
+                    name: "fn".into(),
+                    params: vec![],
+                    value: vec!["test".into()]
+                }
+            )),
+        );
+        assert_eq!(
+            parse_property("fn;type=internet:test,time\r\n"),
+            Ok((
+                "\r\n",
+                Property {
+                    group: None,
+                    name: "fn".into(),
+                    params: vec![("type".into(), "internet".into())],
+                    value: vec!["test,time".into()]
+                }
+            )),
+        );
+
+        assert_eq!(
+            parse_property("item1.TEL;type=pref:+1 555 0100\r\n"),
+            Ok((
+                "\r\n",
+                Property {
+                    group: Some("item1".into()),
+                    name: "TEL".into(),
+                    params: vec![("type".into(), "pref".into())],
+                    value: vec!["+1 555 0100".into()]
+                }
+            )),
+        );
+
+        assert_eq!(
+            parse_properties("fn:test\r\nEND:VCARD\r\n", parse_property_name),
+            Ok((
+                "\r\nEND:VCARD\r\n",
+                vec![Property {
+                    group: None,
+                    name: "fn".into(),
+                    params: vec![],
+                    value: vec!["test".into()]
+                }]
+            )),
+        );
+    }
+
+    #[test]
+    fn vcard() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+
+        assert_eq!(card.properties.len(), 9);
+        assert_eq!(
+            card.get("PHOTO").unwrap().value,
+            vec!["https://lh3.googleusercontent.com/contacts/AOq4LdZ2EOkQkPc_KK2CyLAkx18rcOgp0FYDG3f9_omOYadasd"]
+        );
+        assert_eq!(
+            parse_vcard("FN:Hello Betty\r\n"),
+            Err(ParseError::Syntax { offset: 0 })
+        );
+    }
+
+    #[test]
+    fn unfold_at_end_of_input() {
+        let mut text = "FN:Hello Betty\r\n".to_string();
+        unfold(&mut text);
+        assert_eq!(text, "FN:Hello Betty\r\n");
+
+        let mut text = "FN:Hello\r\n Betty\r".to_string();
+        unfold(&mut text);
+        assert_eq!(text, "FN:HelloBetty\r");
+
+        let mut text = "\r\n".to_string();
+        unfold(&mut text);
+        assert_eq!(text, "\r\n");
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(parse_version("VERSION:3.0\r\n"), Ok(("", Version::V30)));
+        assert_eq!(parse_version("version:3.0\r\n"), Ok(("", Version::V30)));
+        assert_eq!(parse_version("Version:2.1\r\n"), Ok(("", Version::V21)));
+        assert_eq!(parse_version("VERSION : 4.0 \r\n"), Ok(("", Version::V40)));
+        assert!(parse_version("VERSION:5.0\r\n").is_err());
+        assert!(parse_version("VERSION:\r\n").is_err());
+    }
+
+    #[test]
+    fn property_without_colon() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nJUSTNAME\r\nFN:Jane Doe\r\nEND:VCARD\r\n";
+
+        assert_eq!(
+            parse_vcard(text),
+            Err(ParseError::MalformedProperty { line: 3 })
+        );
+
+        let second = format!("BEGIN:VCARD\r\nVERSION:3.0\r\nEND:VCARD\r\n{}", text);
+        assert_eq!(
+            parse_vcards(&second),
+            Err(ParseError::MalformedProperty { line: 6 })
+        );
+
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(text, &options),
+                Err(ParseError::MalformedProperty { line: 3 })
+            );
+        } else {
+            let card = parse_vcard_with(text, &options).unwrap();
+            assert_eq!(card.properties, vec![Property::new("FN", "Jane Doe")]);
+        }
+    }
+
+    #[test]
+    fn structured_values() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+N:Doe;Jane\\;Ann;;;\r
+TEL;VALUE=uri;TYPE=home:tel:+1-555-555-5555;ext=5555\r
+GEO:geo:37.386013,-122.082932;u=35\r
+NOTE:one;two\r
+ORG:http: stuff;dept\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.get("N").unwrap().value,
+            vec!["Doe", "Jane\\;Ann", "", "", ""]
+        );
+        assert_eq!(
+            card.get("TEL").unwrap().value,
+            vec!["tel:+1-555-555-5555;ext=5555"]
+        );
+        assert_eq!(
+            card.get("GEO").unwrap().value,
+            vec!["geo:37.386013,-122.082932;u=35"]
+        );
+        assert_eq!(card.get("NOTE").unwrap().value, vec!["one;two"]);
+        assert_eq!(card.get("ORG").unwrap().value, vec!["http: stuff", "dept"]);
+    }
+
+    #[test]
+    fn stray_block_between_cards() {
+        let text = "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+END:VCARD\r
+\r
+BEGIN:VEVENT\r
+SUMMARY:Lunch\r
+END:VEVENT\r
+BEGIN:VCARD\r
+VERSION:3.0\r
+FN:John Doe\r
+END:VCARD\r
+";
+
+        assert_eq!(parse_vcards(text), Err(ParseError::Syntax { offset: 52 }));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcards_with(text, &options),
+                Err(ParseError::Syntax { offset: 52 })
+            );
+        } else {
+            let cards = parse_vcards_with(text, &options).unwrap();
+            assert_eq!(cards.len(), 2);
+            assert_eq!(cards[0].full_name().as_deref(), Some("Jane Doe"));
+            assert_eq!(cards[1].full_name().as_deref(), Some("John Doe"));
+        }
+    }
+
+    #[test]
+    fn trimmed_values() {
+        let text =
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nN: Doe ;  Jane;;;\r\nNOTE:  al  hello \r\nEND:VCARD\r\n";
+
+        let card = parse_vcard(text).unwrap();
+        assert_eq!(
+            card.get("N").unwrap().value,
+            vec![" Doe ", "  Jane", "", "", ""]
+        );
+        assert_eq!(card.get("NOTE").unwrap().value, vec!["  al  hello "]);
+
+        let options = ParseOptions {
+            trim_values: true,
+            ..Default::default()
+        };
+        let card = parse_vcard_with(text, &options).unwrap();
+        assert_eq!(
+            card.get("N").unwrap().value,
+            vec!["Doe", "Jane", "", "", ""]
+        );
+        assert_eq!(card.get("NOTE").unwrap().value, vec!["al  hello"]);
+    }
+
+    #[test]
+    fn trailing_whitespace_after_last_card() {
+        for trailing in ["\r\n\r\n", "\n\n", "\r\n\t\r\n", "  "] {
+            let text = format!("{}{}{}", TEST_STRING, TEST_STRING, trailing);
+            assert_eq!(
+                parse_vcards(&text).map(|c| c.len()),
+                Ok(2),
+                "{:?}",
+                trailing
+            );
+        }
+
+        let card = parse_vcard(TEST_STRING.trim_end()).unwrap();
+        assert!(card.validate().is_empty());
+
+        let text = format!("{}garbage", TEST_STRING);
+        assert!(parse_vcards(&text).is_err());
+    }
+
+    #[test]
+    fn blank_lines_between_properties() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\n\r\n\r\nEMAIL:jane@example.com\r\n\r\nEND:VCARD\r\n";
+
+        assert_eq!(
+            parse_vcard(text),
+            Err(ParseError::MalformedProperty { line: 4 })
+        );
+
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(text, &options),
+                Err(ParseError::MalformedProperty { line: 4 })
+            );
+        } else {
+            let card = parse_vcard_with(text, &options).unwrap();
+            assert_eq!(card.properties.len(), 2);
+            assert_eq!(card.get("EMAIL").unwrap().value, vec!["jane@example.com"]);
+        }
+    }
+
+    #[test]
+    fn leading_space_in_value() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nNOTE: hello\r\nTITLE: Chief\r\n  Engineer\r\nEND:VCARD\r\n";
+        let card = parse_vcard(text).unwrap();
+
+        assert_eq!(card.get("NOTE").unwrap().value, vec![" hello"]);
+        assert_eq!(card.get("TITLE").unwrap().value, vec![" Chief Engineer"]);
+        assert!(card.to_string().contains("\r\nNOTE: hello\r\n"));
+    }
+
+    #[test]
+    fn unexpected_end() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nEMAIL:jane@example.com\r\nTEL:+1 555 0100\r\nEND:VCARD\r\n";
+        let truncated = &text[..text.find("TEL").unwrap()];
+
+        assert_eq!(
+            parse_vcard(truncated),
+            Err(ParseError::UnexpectedEnd { properties: 2 })
+        );
+        assert_eq!(
+            parse_vcard(&text[..text.find("EMAIL").unwrap() + 3]),
+            Err(ParseError::UnexpectedEnd { properties: 1 })
+        );
+        assert_eq!(
+            parse_vcard("BEGIN:VCARD\r\n"),
+            Err(ParseError::UnexpectedEnd { properties: 0 })
+        );
+    }
+
+    #[test]
+    fn group_names() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nitem10.TEL:+1 555 0100\r\nNOTE:See example.com\r\nURL:https://example.com\r\nEND:VCARD\r\n";
+        let card = parse_vcard(text).unwrap();
+
+        let phone = card.get("TEL").unwrap();
+        assert_eq!(phone.group(), Some("item10"));
+        assert_eq!(card.get("NOTE").unwrap().group(), None);
+        assert_eq!(card.get("NOTE").unwrap().value, vec!["See example.com"]);
+        assert_eq!(card.get("URL").unwrap().value, vec!["https://example.com"]);
+
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nélément1.TEL:+1 555 0100\r\nEND:VCARD\r\n";
+        assert_eq!(
+            parse_vcard(text),
+            Err(ParseError::InvalidPropertyName { line: 4 })
+        );
+    }
+
+    #[test]
+    fn lenient_group_names() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nélément 1.TEL:+1 555 0100\r\nbad.na me:x\r\nEND:VCARD\r\n";
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(text, &options),
+                Err(ParseError::InvalidPropertyName { line: 4 })
+            );
+        } else {
+            let card = parse_vcard_with(text, &options).unwrap();
+            assert_eq!(card.get("TEL").unwrap().group(), Some("élément 1"));
+            assert_eq!(card.properties.len(), 2);
+        }
+    }
+
+    #[test]
+    fn line_without_name() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\n;TYPE=CELL:+1 555 0100\r\n:orphan\r\nEND:VCARD\r\n";
+
+        assert_eq!(
+            parse_vcard(text),
+            Err(ParseError::InvalidPropertyName { line: 4 })
+        );
+
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(text, &options),
+                Err(ParseError::InvalidPropertyName { line: 4 })
+            );
+        } else {
+            let card = parse_vcard_with(text, &options).unwrap();
+            let unknown = card.get_all(UNKNOWN_NAME);
+            assert_eq!(unknown.len(), 2);
+            assert_eq!(unknown[0].to_string(), "X-UNKNOWN;TYPE=CELL:+1 555 0100");
+            assert_eq!(unknown[1].value, vec!["orphan"]);
+        }
+    }
+
+    #[test]
+    fn property_aliases() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nitem1.Mobile:+1 555 0100\r\nFAX:+1 555 0101\r\nEND:VCARD\r\n";
+        let options = ParseOptions {
+            aliases: HashMap::from([
+                ("MOBILE".to_string(), "TEL;TYPE=CELL".to_string()),
+                ("FAX".to_string(), "TEL;FAX".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        let card = parse_vcard_with(text, &options).unwrap();
+        let phones = card.get_all("TEL");
+        if cfg!(feature = "strict") {
+            assert!(phones.is_empty());
+        } else {
+            assert_eq!(phones.len(), 2);
+            assert_eq!(phones[0].to_string(), "item1.TEL;TYPE=CELL:+1 555 0100");
+            assert_eq!(phones[1].types(), vec!["FAX"]);
+        }
+        assert!(parse_vcard(text).unwrap().get("TEL").is_none());
+    }
+
+    #[test]
+    fn lenient_truncated_card() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nNOTE;LANGUAGE=en:Call after 5";
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse_vcard(text),
+            Err(ParseError::UnexpectedEnd { properties: 1 })
+        );
+
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(text, &options),
+                Err(ParseError::UnexpectedEnd { properties: 1 })
+            );
+        } else {
+            let card = parse_vcard_with(text, &options).unwrap();
+            assert_eq!(card.full_name().as_deref(), Some("Jane Doe"));
+            assert_eq!(card.get("NOTE").unwrap().value, vec!["Call after 5"]);
+            assert_eq!(card.get("NOTE").unwrap().param("LANGUAGE"), Some("en"));
+            assert_eq!(card.validate(), vec![crate::Warning::UnexpectedEnd]);
+
+            let both = format!("{}{}", TEST_STRING, text);
+            let cards = parse_vcards_with(&both, &options).unwrap();
+            assert_eq!(cards.len(), 2);
+            assert!(cards[0].validate().is_empty());
+        }
+    }
+
+    #[test]
+    fn raw_source() {
+        let second = "BEGIN:VCARD\r\nVERSION:3.0\r\nBROKEN\r\nFN:John Doe\r\nEND:VCARD\r\n";
+        let text = format!("{}\r\n{}", TEST_STRING, second);
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let mut unfolded = TEST_STRING.to_string();
+        unfold(&mut unfolded);
+        assert_eq!(
+            parse_vcard(TEST_STRING).unwrap().raw(),
+            Some(unfolded.as_str())
+        );
+        assert_eq!(VCard::default().raw(), None);
+
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcards_with(&text, &options),
+                Err(ParseError::MalformedProperty { line: 16 })
+            );
+        } else {
+            let cards = parse_vcards_with(&text, &options).unwrap();
+            assert_eq!(cards[0].raw(), Some(unfolded.as_str()));
+            assert_eq!(cards[1].raw(), Some(second));
+        }
+    }
+
+    #[test]
+    fn reused_buffer() {
+        let other = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:John Doe\r\nEND:VCARD\r\n";
+        let mut cards = Vec::with_capacity(4);
+        let buffer = cards.as_ptr();
+
+        parse_vcards_into(TEST_STRING, &mut cards).unwrap();
+        assert_eq!(cards.len(), 1);
+
+        cards.clear();
+        parse_vcards_into(other, &mut cards).unwrap();
+        parse_vcards_into(other, &mut cards).unwrap();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards.as_ptr(), buffer);
+        assert_eq!(cards[1].full_name().as_deref(), Some("John Doe"));
+
+        assert!(parse_vcards_into("BEGIN:VCARD\r\n", &mut cards).is_err());
+        assert_eq!(cards.len(), 2);
+    }
+
+    #[test]
+    fn quoted_printable_soft_breaks() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:2.1\r
+NOTE;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:Gr=C3=BC=C3=9Fe aus =\r
+M=C3=BCnchen=\r
+ und Wien\r
+TITLE:Equals=\r
+FN:Jane Doe\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let note = &card.get("NOTE").unwrap().value[0];
+        assert_eq!(note, "Gr=C3=BC=C3=9Fe aus M=C3=BCnchen und Wien");
+        assert_eq!(
+            String::from_utf8(decode_quoted_printable(note)).unwrap(),
+            "Grüße aus München und Wien"
+        );
+        assert_eq!(card.get("TITLE").unwrap().value, vec!["Equals="]);
+    }
+
+    #[test]
+    fn soft_break_before_end() {
+        let text = "BEGIN:VCARD\r
+VERSION:2.1\r
+FN:Jane Doe\r
+NOTE;ENCODING=QUOTED-PRINTABLE:Bis bald=\r
+END:VCARD\r
+";
+
+        let card = parse_vcard(text).unwrap();
+        assert_eq!(card.get("NOTE").unwrap().value, vec!["Bis bald="]);
+        assert!(matches!(join_soft_breaks(TEST_STRING), Cow::Borrowed(_)));
+        assert!(matches!(join_soft_breaks(text), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn misfolded_base64() {
+        let input = "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+PHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUw\r
+DQYJKoZIhvcNAQEEBQAwdzEL\r
+MAkGA1UEBhMCVVMx\r
+NOTE:Camera\r
+END:VCARD\r
+";
+
+        assert_eq!(
+            parse_vcard(input),
+            Err(ParseError::MalformedProperty { line: 5 })
+        );
+
+        let options = ParseOptions {
+            join_base64_lines: true,
+            ..ParseOptions::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(input, &options),
+                Err(ParseError::MalformedProperty { line: 5 })
+            );
+        } else {
+            let card = parse_vcard_with(input, &options).unwrap();
+            assert_eq!(
+                card.get("PHOTO").unwrap().value,
+                vec!["MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcNAQEEBQAwdzELMAkGA1UEBhMCVVMx"]
+            );
+            assert_eq!(card.get("NOTE").unwrap().value, vec!["Camera"]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    fn strict_ignores_leniency() {
+        let input = "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+PHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUw\r
+DQYJKoZIhvcNAQEEBQAwdzEL\r
+END:VCARD\r
+";
+        let options = ParseOptions {
+            lenient: true,
+            join_base64_lines: true,
+            any_property_name: true,
+            ..ParseOptions::default()
+        };
+
+        assert_eq!(
+            parse_vcard_with(input, &options),
+            Err(ParseError::MalformedProperty { line: 5 })
+        );
+    }
+
+    #[test]
+    fn caret_escaped_parameter() {
+        let (_, property) =
+            parse_property("ADR;LABEL=\"Main St^nSuite ^'4^'\";X-NOTE=x^^2:;;Main St\r\n").unwrap();
+
+        assert_eq!(property.param("LABEL"), Some("Main St\nSuite \"4\""));
+        assert_eq!(property.param("X-NOTE"), Some("x^2"));
+        assert_eq!(
+            property.to_string(),
+            "ADR;LABEL=Main St^nSuite ^'4^';X-NOTE=x^^2:;;Main St"
+        );
+    }
+
+    #[test]
+    fn colons_in_parameters() {
+        let (_, property) =
+            parse_property("GEO;ALTID=1;TYPE=work:geo:37.386013,-122.082932\r\n").unwrap();
+        assert_eq!(
+            property.params,
+            vec![("ALTID".into(), "1".into()), ("TYPE".into(), "work".into())]
+        );
+        assert_eq!(property.value, vec!["geo:37.386013,-122.082932"]);
+
+        let (_, property) =
+            parse_property("KEY;X-REF=\"urn:uuid:f81d4fae\";TYPE=pgp:https://example.com/k\r\n")
+                .unwrap();
+        assert_eq!(property.param("X-REF"), Some("urn:uuid:f81d4fae"));
+        assert_eq!(property.value, vec!["https://example.com/k"]);
+
+        let (_, property) = parse_property("X-LINK;X-REF=urn:uuid:f81d4fae\r\n").unwrap();
+        assert_eq!(property.param("X-REF"), Some("urn"));
+        assert_eq!(property.value, vec!["uuid:f81d4fae"]);
+    }
+
+    #[test]
+    fn extension_components() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+X-ANDROID-CUSTOM:vnd.android.cursor.item/relation;John Doe;1;;;;;;;;;;;;;\r
+X-NOTE:a\\;b\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let custom = card.get("X-ANDROID-CUSTOM").unwrap();
+        assert_eq!(custom.components().len(), 16);
+        assert_eq!(
+            custom.components()[..3],
+            ["vnd.android.cursor.item/relation", "John Doe", "1"]
+        );
+        assert_eq!(card.get("X-NOTE").unwrap().components(), ["a\\;b"]);
+    }
+
+    #[test]
+    fn try_from_str() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n";
+        let card = VCard::try_from(text).unwrap();
+        assert!(matches!(card.properties[0].value[0], Cow::Borrowed(_)));
+        assert_eq!(card.full_name().as_deref(), Some("Jane Doe"));
+
+        let owned: VCard<'static> = text.parse().unwrap();
+        assert_eq!(owned, card);
+        assert_eq!(
+            VCard::try_from("BEGIN:VCARD\r\n"),
+            Err(ParseError::UnexpectedEnd { properties: 0 })
+        );
+    }
+
+    #[test]
+    fn version_after_other_properties() {
+        let text = "BEGIN:VCARD\r\nFN:Jane Doe\r\nN:Doe;Jane;;;\r\nVERSION:4.0\r\nEND:VCARD\r\n";
+        let card = parse_vcard(text).unwrap();
+        assert_eq!(card.version(), Version::V40);
+        assert_eq!(card.properties.len(), 2);
+        assert!(card.get("VERSION").is_none());
+
+        let missing = "BEGIN:VCARD\r\nFN:Jane Doe\r\nEND:VCARD\r\n";
+        assert_eq!(parse_vcard(missing), Err(ParseError::MissingVersion));
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(missing, &options),
+                Err(ParseError::MissingVersion)
+            );
+        } else {
+            assert_eq!(
+                parse_vcard_with(missing, &options).unwrap().version(),
+                Version::V30
+            );
+        }
+    }
+
+    #[test]
+    fn locate_error() {
+        let text = TEST_STRING.replace("ROLE:Application", "ROLE;Application");
+        let Err(ParseError::MalformedProperty { line }) = parse_vcard(&text) else {
+            panic!("expected a malformed property");
+        };
+
+        let offset = text.find("ROLE;").unwrap();
+        let location = locate(&text, offset).unwrap();
+        assert_eq!(location.line, line);
+        assert_eq!(location.line, 8);
+        assert!(location
+            .tail
+            .starts_with("ROLE;Application Engineer\r\nNOTE"));
+
+        assert_eq!(locate(TEST_STRING, 0).unwrap().line, 1);
+        assert_eq!(locate(TEST_STRING, TEST_STRING.len() + 1), None);
+    }
+
+    #[test]
+    fn unfold_strips_one_whitespace() {
+        let mut text = "NOTE:one\r\n  two\r\n\tthree\r\n\t four\r\n".to_string();
+        unfold(&mut text);
+        assert_eq!(text, "NOTE:one twothree four\r\n");
+
+        let card = parse_vcard(TEST_STRING).unwrap();
+        assert!(card.get("PHOTO").unwrap().value[0].contains("AOq4LdZ2EOkQkPc_KK2CyLAkx18rcOgp0"));
+    }
+
+    #[test]
+    fn repeated_and_bare_parameters() {
+        let (_, property) =
+            parse_property("ADR;TYPE=HOME;TYPE=WORK:;;123 Main St;Springfield\r\n").unwrap();
+        assert_eq!(property.types(), vec!["HOME", "WORK"]);
+
+        let (_, property) = parse_property("TEL;TYPE=HOME;VOICE;work:+1 555 0100\r\n").unwrap();
+        assert_eq!(
+            property.params,
+            vec![
+                ("TYPE".into(), "HOME".into()),
+                ("TYPE".into(), "VOICE".into()),
+                ("TYPE".into(), "work".into())
+            ]
+        );
+        assert_eq!(property.value, vec!["+1 555 0100"]);
+
+        let (_, property) = parse_property("PHOTO;JPEG;BASE64:TWFu\r\n").unwrap();
+        assert_eq!(property.param("ENCODING"), Some("BASE64"));
+        assert_eq!(property.param("TYPE"), Some("JPEG"));
+    }
+
+    #[test]
+    fn property_name_characters() {
+        assert_eq!(
+            parse_property_name("item1.X-ABLabel:Work"),
+            Ok((":Work", "item1.X-ABLabel"))
+        );
+        assert!(parse_property_name("FULL NAME:Jane").is_err());
+        assert!(parse_property_name("a.b.c:Jane").is_err());
+
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFULL NAME:Jane Doe\r\nEND:VCARD\r\n";
+        assert_eq!(
+            parse_vcard(text),
+            Err(ParseError::InvalidPropertyName { line: 3 })
+        );
+
+        let options = ParseOptions {
+            any_property_name: true,
+            ..ParseOptions::default()
+        };
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                parse_vcard_with(text, &options),
+                Err(ParseError::InvalidPropertyName { line: 3 })
+            );
+        } else {
+            let card = parse_vcard_with(text, &options).unwrap();
+            assert_eq!(card.get("FULL NAME").unwrap().value, vec!["Jane Doe"]);
+        }
+    }
+
+    #[test]
+    fn parse_one_returns_tail() {
+        let second = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n";
+        let text = format!("{}{}", TEST_STRING, second);
+
+        let (card, tail) = parse_one(&text).unwrap();
+        assert_eq!(card.full_name().as_deref(), Some("Hello Betty"));
+        assert_eq!(tail, second);
+
+        let (card, tail) = parse_one(tail).unwrap();
+        assert_eq!(card.full_name().as_deref(), Some("Jane Doe"));
+        assert_eq!(tail, "");
+    }
+
+    #[test]
+    fn truncated_final_card() {
+        let text = format!("{}BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane", TEST_STRING);
+        assert_eq!(
+            parse_vcards(&text),
+            Err(ParseError::UnexpectedEnd { properties: 0 })
+        );
+
+        let (cards, error) = parse_vcards_partial(&text);
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].full_name().as_deref(), Some("Hello Betty"));
+        assert_eq!(error, Some(ParseError::UnexpectedEnd { properties: 0 }));
+
+        let (cards, error) = parse_vcards_partial("BEGIN:VCARD");
+        assert!(cards.is_empty());
+        assert!(error.is_some());
+        assert_eq!(parse_vcards_partial(TEST_STRING).1, None);
+    }
+
+    #[test]
+    fn semicolons_in_parameter_values() {
+        let (_, property) =
+            parse_property("TEL;TYPE=\"a;b\";X-NOTE=c\\;d;PREF=1:+1 555 0100\r\n").unwrap();
+        assert_eq!(
+            property.params,
+            vec![
+                ("TYPE".into(), "a;b".into()),
+                ("X-NOTE".into(), "c\\;d".into()),
+                ("PREF".into(), "1".into())
+            ]
+        );
+        assert_eq!(
+            property.to_string(),
+            "TEL;TYPE=\"a;b\";X-NOTE=\"c\\;d\";PREF=1:+1 555 0100"
+        );
+    }
+}
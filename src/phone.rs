@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+use crate::escape::percent_decode;
+use crate::property::Property;
+use crate::vcard::VCard;
+
+/// The value of a `TEL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phone<'a> {
+    /// The number, without the `tel:` scheme of a 4.0 URI. Percent escapes
+    /// in a URI are decoded.
+    pub number: Cow<'a, str>,
+    /// The parameters of a `tel:` URI, such as `("ext", "5555")` or
+    /// `("phone-context", "example.com")`, in order. Plain 3.0 numbers have
+    /// none. Values are percent-decoded.
+    pub uri_params: Vec<(&'a str, Cow<'a, str>)>,
+}
+
+fn phone<'p>(property: &'p Property<'_>) -> Option<Phone<'p>> {
+    let value = property.value.first()?.as_ref();
+
+    let uri = value
+        .get(.."tel:".len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case("tel:"))
+        .map(|_| &value["tel:".len()..]);
+    let Some(uri) = uri else {
+        return Some(Phone {
+            number: Cow::Borrowed(value),
+            uri_params: vec![],
+        });
+    };
+
+    let mut parts = uri.split(';');
+    let number = percent_decode(parts.next().unwrap_or_default());
+    let uri_params = parts
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (name, value) = part.split_once('=').unwrap_or((part, ""));
+            (name, percent_decode(value))
+        })
+        .collect();
+
+    Some(Phone { number, uri_params })
+}
+
+impl VCard<'_> {
+    /// Every `TEL`, in card order.
+    pub fn phones(&self) -> Vec<Phone<'_>> {
+        self.get_all("TEL").into_iter().filter_map(phone).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_vcard, TEST_STRING};
+
+    #[test]
+    fn tel_uri() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+TEL;VALUE=uri;TYPE=work:tel:+1-555-555-5555;ext=5555;phone-context=example.com\r
+TEL;VALUE=uri:TEL:%2B1-555-555-0000\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.phones(),
+            vec![
+                Phone {
+                    number: "+1-555-555-5555".into(),
+                    uri_params: vec![
+                        ("ext", "5555".into()),
+                        ("phone-context", "example.com".into())
+                    ],
+                },
+                Phone {
+                    number: "+1-555-555-0000".into(),
+                    uri_params: vec![],
+                },
+            ]
+        );
+
+        let card = parse_vcard(TEST_STRING).unwrap();
+        assert_eq!(card.phones()[0].number, "+91 12342 12332");
+        assert!(card.phones()[0].uri_params.is_empty());
+    }
+}
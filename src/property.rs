@@ -0,0 +1,288 @@
+use std::borrow::Cow;
+
+use crate::escape::{escape_value, split_list, unescape_value, LIST_PROPERTIES};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Property<'a> {
+    pub group: Option<Cow<'a, str>>,
+    pub name: Cow<'a, str>,
+    pub params: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    /// The raw, still escaped, value. Structured properties such as `N` and
+    /// `ADR`, and `X-` extensions, have one entry per component.
+    pub value: Vec<Cow<'a, str>>,
+}
+
+/// A [`Property`] that owns all of its text.
+pub type PropertyOwned = Property<'static>;
+
+impl<'a> Property<'a> {
+    /// Creates an ungrouped property with no parameters and a single,
+    /// already escaped, value.
+    pub fn new(name: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) -> Self {
+        Property {
+            group: None,
+            name: name.into(),
+            params: vec![],
+            value: vec![value.into()],
+        }
+    }
+
+    /// Whether this property is called `name`, ignoring case. Two properties
+    /// match the same names exactly when their [`canonical_name`]s are equal.
+    ///
+    /// [`canonical_name`]: Property::canonical_name
+    pub fn is(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// Whether this property is called `name` and, if `kind` is given, has
+    /// it among its [`types`](Property::types), all ignoring case:
+    /// `matches("TEL", Some("CELL"))` picks out mobile numbers.
+    pub fn matches(&self, name: &str, kind: Option<&str>) -> bool {
+        self.is(name)
+            && kind.is_none_or(|kind| self.types().iter().any(|t| t.eq_ignore_ascii_case(kind)))
+    }
+
+    /// The group the property belongs to, e.g. `item1` in `item1.TEL`.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Whether the property belongs to `group`. Group names ignore case.
+    pub fn in_group(&self, group: &str) -> bool {
+        self.group().is_some_and(|g| g.eq_ignore_ascii_case(group))
+    }
+
+    /// Whether the property is a private or vendor extension rather than one
+    /// the specification defines: an `X-` name, a 4.0 `VND-` name, or a
+    /// name under `VND.`, which the grammar reads as the group `VND`.
+    pub fn is_extension(&self) -> bool {
+        let prefixed = |prefix: &str| {
+            self.name
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        };
+        prefixed("X-") || prefixed("VND-") || self.in_group("VND")
+    }
+
+    /// The name in upper case, `X-` extensions included, for use as a map key.
+    pub fn canonical_name(&self) -> String {
+        self.name.to_ascii_uppercase()
+    }
+
+    /// The value of the first parameter called `name`. Parameter names ignore
+    /// case, so `param("TYPE")` also finds `type=internet`.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_ref())
+    }
+
+    /// Adds a parameter after any existing ones, even if one with the same
+    /// name is already there. Values are stored as they read: quoting and
+    /// escaping are applied when the property is written.
+    pub fn add_param(&mut self, name: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) {
+        self.params.push((name.into(), value.into()));
+    }
+
+    /// Replaces every parameter called `name` with one holding `value`, in
+    /// the place of the first one, or adds it if there was none.
+    pub fn set_param(&mut self, name: &str, value: impl Into<Cow<'a, str>>) {
+        let position = self
+            .params
+            .iter()
+            .position(|(n, _)| n.eq_ignore_ascii_case(name));
+        match position {
+            Some(i) => {
+                let (existing, _) = self.params.remove(i);
+                self.remove_param(name);
+                self.params.insert(i, (existing, value.into()));
+            }
+            None => self.add_param(name.to_string(), value),
+        }
+    }
+
+    /// Removes every parameter called `name`, returning how many there were.
+    pub fn remove_param(&mut self, name: &str) -> usize {
+        let before = self.params.len();
+        self.params.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        before - self.params.len()
+    }
+
+    /// Every `TYPE` on the property, with comma-separated lists split apart.
+    pub fn types(&self) -> Vec<&str> {
+        self.params
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("TYPE"))
+            .flat_map(|(_, value)| value.split(','))
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    /// The raw value's components: one per `;`-separated part for structured
+    /// properties and `X-` extensions, otherwise just the whole value.
+    pub fn components(&self) -> &[Cow<'a, str>] {
+        &self.value
+    }
+
+    /// Whether the value is a `,`-separated list, as for `CATEGORIES` and
+    /// `NICKNAME`. Elsewhere, such as in `FN` or `NOTE`, a comma is text.
+    pub fn is_list(&self) -> bool {
+        LIST_PROPERTIES.iter().any(|name| self.is(name))
+    }
+
+    /// The unescaped items of a list property, or the whole unescaped value
+    /// as the only item of any other.
+    pub fn list_values(&self) -> Vec<String> {
+        let raw = self.raw_value();
+        if !self.is_list() {
+            return vec![unescape_value(&raw).into_owned()];
+        }
+
+        split_list(&raw)
+            .into_iter()
+            .map(|item| unescape_value(item).into_owned())
+            .collect()
+    }
+
+    /// Replaces the value with `items`, the reverse of [`list_values`]: each
+    /// item is escaped, so a comma inside one is written `\,`, and the items
+    /// are joined by bare commas. Only list properties read them back apart.
+    ///
+    /// [`list_values`]: Property::list_values
+    pub fn set_list_values<S: AsRef<str>>(&mut self, items: impl IntoIterator<Item = S>) {
+        let value = items
+            .into_iter()
+            .map(|item| escape_value(item.as_ref()).into_owned())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.value = vec![Cow::Owned(value)];
+    }
+
+    /// The raw value with every component joined back together.
+    pub fn raw_value(&self) -> Cow<'_, str> {
+        match self.value.as_slice() {
+            [value] => Cow::Borrowed(value),
+            values => Cow::Owned(values.join(";")),
+        }
+    }
+
+    pub fn into_owned(self) -> PropertyOwned {
+        Property {
+            group: self.group.map(|g| Cow::Owned(g.into_owned())),
+            name: Cow::Owned(self.name.into_owned()),
+            params: self
+                .params
+                .into_iter()
+                .map(|(k, v)| (Cow::Owned(k.into_owned()), Cow::Owned(v.into_owned())))
+                .collect(),
+            value: self
+                .value
+                .into_iter()
+                .map(|v| Cow::Owned(v.into_owned()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn canonical_name() {
+        let mut by_name: HashMap<String, Vec<Property>> = HashMap::new();
+        for name in ["fn", "FN", "Fn", "x-Custom"] {
+            let property = Property::new(name, "Jane");
+            by_name
+                .entry(property.canonical_name())
+                .or_default()
+                .push(property);
+        }
+
+        assert_eq!(by_name["FN"].len(), 3);
+        assert_eq!(by_name["X-CUSTOM"].len(), 1);
+        assert!(by_name["FN"].iter().all(|p| p.is("fN")));
+    }
+
+    #[test]
+    fn param_ignores_case() {
+        let mut upper = Property::new("EMAIL", "hello.betty@gmail.com");
+        upper.params.push(("TYPE".into(), "INTERNET".into()));
+        let mut lower = Property::new("EMAIL", "hello.betty@gmail.com");
+        lower.params.push(("type".into(), "internet".into()));
+
+        for name in ["TYPE", "type", "Type"] {
+            assert_eq!(upper.param(name), Some("INTERNET"));
+            assert_eq!(lower.param(name), Some("internet"));
+        }
+        assert_eq!(upper.param("PREF"), None);
+    }
+
+    #[test]
+    fn matches() {
+        let card = crate::parse_vcard(crate::TEST_STRING).unwrap();
+
+        let cells: Vec<&str> = card
+            .properties
+            .iter()
+            .filter(|p| p.matches("tel", Some("cell")))
+            .map(|p| p.value[0].as_ref())
+            .collect();
+        assert_eq!(cells, vec!["+91 12342 12332", "+1 (123) 112-123"]);
+        assert!(card.get("EMAIL").unwrap().matches("EMAIL", None));
+        assert!(!card.get("EMAIL").unwrap().matches("EMAIL", Some("CELL")));
+    }
+
+    #[test]
+    fn list_values() {
+        let categories = Property::new("categories", "Friends,Rock\\, Paper,Work");
+        assert!(categories.is_list());
+        assert_eq!(
+            categories.list_values(),
+            vec!["Friends", "Rock, Paper", "Work"]
+        );
+
+        let name = Property::new("FN", "test,time");
+        assert!(!name.is_list());
+        assert_eq!(name.list_values(), vec!["test,time"]);
+    }
+
+    #[test]
+    fn write_list_values() {
+        let mut categories = Property::new("CATEGORIES", "");
+        categories.set_list_values(["Friends", "Rock, Paper", "Work"]);
+
+        assert_eq!(
+            categories.to_string(),
+            "CATEGORIES:Friends,Rock\\, Paper,Work"
+        );
+        assert_eq!(
+            categories.list_values(),
+            vec!["Friends", "Rock, Paper", "Work"]
+        );
+    }
+
+    #[test]
+    fn edit_params() {
+        let mut property = Property::new("EMAIL", "jane@example.com");
+        property.add_param("TYPE", "INTERNET");
+        property.add_param("TYPE", "WORK");
+        property.add_param("X-LABEL", "Office; \"main\"");
+        assert_eq!(
+            property.to_string(),
+            "EMAIL;TYPE=INTERNET;TYPE=WORK;X-LABEL=\"Office; ^'main^'\":jane@example.com"
+        );
+
+        property.set_param("type", "HOME");
+        assert_eq!(property.remove_param("x-label"), 1);
+        assert_eq!(property.to_string(), "EMAIL;TYPE=HOME:jane@example.com");
+
+        property.set_param("PREF", "1");
+        assert_eq!(property.param("PREF"), Some("1"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+
+use crate::vcard::{VCard, VCardOwned};
+
+/// Properties that hold personal data.
+const SENSITIVE: &[&str] = &["ADR", "EMAIL", "NOTE", "PHOTO", "TEL"];
+
+/// What a redacted value component reads.
+const PLACEHOLDER: &str = "***";
+
+impl VCard<'_> {
+    /// A copy of the card that is safe to log: the values of `TEL`, `EMAIL`,
+    /// `ADR`, `PHOTO` and `NOTE` read `***`, while the names, groups and
+    /// parameters of every property stay as they were. Empty components of
+    /// structured values stay empty, so their layout is still visible. The
+    /// [`raw`](VCard::raw) source text is dropped.
+    pub fn redact(&self) -> VCardOwned {
+        let mut card = self.clone().into_owned();
+        // The source text holds every value in the clear.
+        card.raw = None;
+
+        for property in card.properties.iter_mut() {
+            if !SENSITIVE.iter().any(|name| property.is(name)) {
+                continue;
+            }
+
+            for value in property.value.iter_mut() {
+                if !value.is_empty() {
+                    *value = Cow::Borrowed(PLACEHOLDER);
+                }
+            }
+        }
+
+        card
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_vcard, TEST_STRING};
+
+    #[test]
+    fn redact() {
+        let card = parse_vcard(
+            &TEST_STRING.replace("ROLE:", "ADR;TYPE=HOME:;;1 Main St;Springfield;;;\r\nROLE:"),
+        )
+        .unwrap()
+        .redact();
+
+        assert_eq!(card.full_name().as_deref(), Some("Hello Betty"));
+        assert_eq!(card.get("EMAIL").unwrap().value, vec!["***"]);
+        assert_eq!(
+            card.get("EMAIL").unwrap().params,
+            vec![("TYPE".into(), "INTERNET".into())]
+        );
+        assert!(card.get_all("TEL").iter().all(|tel| tel.value == ["***"]));
+        assert_eq!(card.get("ADR").unwrap().raw_value(), ";;***;***;;;");
+        assert_eq!(card.get("NOTE").unwrap().value, vec!["***"]);
+        assert_eq!(card.get("PHOTO").unwrap().value, vec!["***"]);
+        assert_eq!(
+            card.get("ROLE").unwrap().value,
+            vec!["Application Engineer"]
+        );
+    }
+
+    #[test]
+    fn redact_drops_source_text() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+        assert!(card.raw().is_some());
+
+        let redacted = card.redact();
+        assert!(redacted.raw().is_none());
+
+        let debug = format!("{:?}", redacted);
+        for secret in [
+            "hello.betty@gmail.com",
+            "12342 12332",
+            "112-123",
+            "Gender: Male",
+        ] {
+            assert!(!debug.contains(secret), "{}", secret);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Properties RFC 6715 adds to 4.0 for the Open Mobile Alliance.
+
+use crate::escape::unescape_value;
+use crate::property::Property;
+use crate::vcard::VCard;
+
+/// An `EXPERTISE`, `HOBBY` or `INTEREST`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeveledValue<'a> {
+    /// The unescaped value, e.g. `chemistry`.
+    pub value: String,
+    /// `beginner`, `average` or `expert` for expertise, `high`, `medium` or
+    /// `low` otherwise.
+    pub level: Option<&'a str>,
+    /// The position among properties of the same name, from 1.
+    pub index: Option<u32>,
+}
+
+impl<'a> VCard<'a> {
+    /// Every `EXPERTISE`, in card order.
+    pub fn expertise(&self) -> Vec<LeveledValue<'_>> {
+        self.leveled("EXPERTISE")
+    }
+
+    /// Every `HOBBY`, in card order.
+    pub fn hobbies(&self) -> Vec<LeveledValue<'_>> {
+        self.leveled("HOBBY")
+    }
+
+    /// Every `INTEREST`, in card order.
+    pub fn interests(&self) -> Vec<LeveledValue<'_>> {
+        self.leveled("INTEREST")
+    }
+
+    /// The `INDEX` and URI of every `ORG-DIRECTORY`, a directory of the
+    /// contact's organization, in `INDEX` order. Ones without an `INDEX` come
+    /// last, in card order.
+    pub fn org_directory(&self) -> Vec<(Option<u32>, &str)> {
+        let mut directories: Vec<_> = self
+            .get_all("ORG-DIRECTORY")
+            .into_iter()
+            .filter_map(|p| Some((index(p), p.value.first()?.as_ref())))
+            .collect();
+        directories.sort_by_key(|&(index, _)| (index.is_none(), index));
+        directories
+    }
+
+    fn leveled(&self, name: &str) -> Vec<LeveledValue<'_>> {
+        self.get_all(name)
+            .into_iter()
+            .map(|p| LeveledValue {
+                value: unescape_value(&p.raw_value()).into_owned(),
+                level: p.param("LEVEL"),
+                index: index(p),
+            })
+            .collect()
+    }
+}
+
+fn index(property: &Property<'_>) -> Option<u32> {
+    property.param("INDEX")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_vcard;
+
+    #[test]
+    fn expertise() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+EXPERTISE;LEVEL=expert;INDEX=1:chemistry\r
+EXPERTISE;INDEX=2:chinese literature\r
+HOBBY;LEVEL=high:reading\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.expertise(),
+            vec![
+                LeveledValue {
+                    value: "chemistry".to_string(),
+                    level: Some("expert"),
+                    index: Some(1),
+                },
+                LeveledValue {
+                    value: "chinese literature".to_string(),
+                    level: None,
+                    index: Some(2),
+                },
+            ]
+        );
+        assert_eq!(card.hobbies()[0].level, Some("high"));
+        assert!(card.interests().is_empty());
+    }
+
+    #[test]
+    fn org_directory() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+ORG-DIRECTORY:http://directory.example.org\r
+ORG-DIRECTORY;INDEX=4:ldap://ldap.tech.example/o=Example%20Tech,ou=Engineering\r
+ORG-DIRECTORY;INDEX=x:http://old.example.org\r
+ORG-DIRECTORY;PREF=1;INDEX=1:http://directory.mycompany.example.com\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.org_directory(),
+            vec![
+                (Some(1), "http://directory.mycompany.example.com"),
+                (
+                    Some(4),
+                    "ldap://ldap.tech.example/o=Example%20Tech,ou=Engineering"
+                ),
+                (None, "http://directory.example.org"),
+                (None, "http://old.example.org"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use crate::escape::unescape_value;
+use crate::vcard::VCard;
+
+/// The properties [`search`] looks in.
+const SEARCHED: &[&str] = &["EMAIL", "FN", "N", "ORG", "TEL"];
+
+/// Every card with `query` somewhere in its `FN`, `N`, `EMAIL`, `TEL` or
+/// `ORG`, ignoring case, as a contact picker would offer them. Cards are
+/// returned in the order given, each at most once, and an empty query
+/// matches them all.
+pub fn search<'c, 'a>(cards: &'c [VCard<'a>], query: &str) -> Vec<&'c VCard<'a>> {
+    let query = query.to_lowercase();
+
+    cards
+        .iter()
+        .filter(|card| {
+            card.properties
+                .iter()
+                .filter(|p| SEARCHED.iter().any(|name| p.is(name)))
+                .any(|p| {
+                    unescape_value(&p.raw_value())
+                        .to_lowercase()
+                        .contains(&query)
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_vcards;
+
+    #[test]
+    fn search_names_and_emails() {
+        let cards = parse_vcards(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+EMAIL:jane@example.com\r
+END:VCARD\r
+BEGIN:VCARD\r
+VERSION:3.0\r
+FN:John Smith\r
+EMAIL:jsmith@acme.test\r
+NOTE:Knows Jane\r
+END:VCARD\r
+BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Ann Lee\r
+ORG:Acme\\, Inc.;Sales\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let names = |query: &str| -> Vec<String> {
+            search(&cards, query)
+                .iter()
+                .map(|card| card.full_name().unwrap())
+                .collect()
+        };
+        assert_eq!(names("jane"), vec!["Jane Doe"]);
+        assert_eq!(names("ACME"), vec!["John Smith", "Ann Lee"]);
+        assert_eq!(names("acme, inc"), vec!["Ann Lee"]);
+        assert!(names("nobody").is_empty());
+        assert_eq!(names("").len(), 3);
+    }
+}
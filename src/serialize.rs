@@ -0,0 +1,489 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+
+use crate::convert::{upgrade_media, MEDIA};
+use crate::escape::encode_param_value;
+use crate::media::is_base64;
+use crate::property::Property;
+use crate::vcard::VCard;
+use crate::version::Version;
+
+/// Longest content line, in octets, before it is folded.
+const MAX_LINE: usize = 75;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Leave out cards for which [`VCard::is_empty`] holds.
+    pub skip_empty: bool,
+    /// Write properties that only differ in their `TYPE`s, such as the same
+    /// address as both `EMAIL;TYPE=HOME` and `EMAIL;TYPE=WORK`, as one with
+    /// the types combined.
+    pub merge_duplicate_types: bool,
+    /// Write `FN` and `N` straight after `VERSION`, as some importers
+    /// expect, and everything else after them in card order.
+    pub canonical_order: bool,
+    /// Put an empty line between cards, as some exports do for
+    /// readability. Parsing skips it.
+    pub blank_line_between: bool,
+}
+
+/// Properties that [`SerializeOptions::canonical_order`] writes first.
+const LEADING: &[&str] = &["FN", "N"];
+
+/// Writes a content line followed by CRLF, folding it so that no physical
+/// line is longer than [`MAX_LINE`] octets.
+fn write_folded(f: &mut impl fmt::Write, line: &str) -> fmt::Result {
+    let mut rest = line;
+    let mut limit = MAX_LINE;
+
+    while rest.len() > limit {
+        let mut end = limit;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        f.write_str(&rest[..end])?;
+        f.write_str("\r\n ")?;
+        rest = &rest[end..];
+        // The leading space of a continuation counts towards its length.
+        limit = MAX_LINE - 1;
+    }
+
+    f.write_str(rest)?;
+    f.write_str("\r\n")
+}
+
+fn needs_quotes(value: &str) -> bool {
+    value.contains([':', ';'])
+}
+
+/// Drops the types in a `TYPE` list that an earlier one on the property
+/// already gave, ignoring case, and keeps the rest in order. `None` if none
+/// are left.
+fn dedup_types<'v>(value: &'v str, seen: &mut Vec<&'v str>) -> Option<Cow<'v, str>> {
+    let before = seen.len();
+    let mut duplicates = false;
+    for t in value.split(',') {
+        if seen.iter().any(|s| s.eq_ignore_ascii_case(t)) {
+            duplicates = true;
+        } else {
+            seen.push(t);
+        }
+    }
+
+    match &seen[before..] {
+        [] => None,
+        _ if !duplicates => Some(Cow::Borrowed(value)),
+        fresh => Some(Cow::Owned(fresh.join(","))),
+    }
+}
+
+/// Writes the unfolded content line, without its CRLF.
+impl fmt::Display for Property<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(group) = &self.group {
+            write!(f, "{}.", group)?;
+        }
+        f.write_str(&self.name)?;
+
+        let mut types: Vec<&str> = vec![];
+        for (name, value) in self.params.iter() {
+            let value = if name.eq_ignore_ascii_case("TYPE") {
+                match dedup_types(value, &mut types) {
+                    Some(value) => value,
+                    None => continue,
+                }
+            } else {
+                Cow::Borrowed(value.as_ref())
+            };
+            let value = encode_param_value(&value);
+            if needs_quotes(&value) {
+                write!(f, ";{}=\"{}\"", name, value)?;
+            } else {
+                write!(f, ";{}={}", name, value)?;
+            }
+        }
+
+        write!(f, ":{}", self.value.join(";"))
+    }
+}
+
+/// Writes the whole card, from `BEGIN:VCARD` to `END:VCARD`, with folded
+/// CRLF-terminated lines.
+///
+/// 4.0 only allows binary data as a URI, so inline `ENCODING=b` media such
+/// as a `PHOTO` on a 4.0 card is written as a `data:` URI.
+impl fmt::Display for VCard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_folded(f, "BEGIN:VCARD")?;
+        write_folded(f, &format!("VERSION:{}", self.version()))?;
+
+        for property in self.properties.iter().filter(|p| !p.is("VERSION")) {
+            let inline_media = self.version() == Version::V40
+                && MEDIA.iter().any(|name| property.is(name))
+                && is_base64(property);
+            if inline_media {
+                let mut property = property.clone();
+                upgrade_media(&mut property);
+                write_folded(f, &property.to_string())?;
+            } else {
+                write_folded(f, &property.to_string())?;
+            }
+        }
+
+        write_folded(f, "END:VCARD")
+    }
+}
+
+impl VCard<'_> {
+    /// Streams the card, as [`Display`](fmt::Display) would write it, to `w`
+    /// without building the whole text first.
+    pub fn to_writer<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "{}", self)
+    }
+}
+
+/// Streams `cards` one after another to `w`, for exports too big to hold as
+/// one string. A `BufWriter` around files and sockets avoids a write call
+/// per line.
+pub fn write_vcards<W: io::Write>(cards: &[VCard<'_>], mut w: W) -> io::Result<()> {
+    for card in cards {
+        card.to_writer(&mut w)?;
+    }
+    Ok(())
+}
+
+/// Serializes `cards` one after another into a single `.vcf` text, each
+/// from its `BEGIN:VCARD` to its `END:VCARD`. [`parse_vcards`] reads them
+/// back. The text depends only on the cards and options, so exports of the
+/// same cards diff cleanly.
+///
+/// [`parse_vcards`]: crate::parse_vcards
+pub fn serialize_vcards(cards: &[VCard<'_>], options: &SerializeOptions) -> String {
+    let separator = if options.blank_line_between {
+        "\r\n"
+    } else {
+        ""
+    };
+
+    cards
+        .iter()
+        .filter(|card| !(options.skip_empty && card.is_empty()))
+        .map(|card| {
+            if !options.merge_duplicate_types && !options.canonical_order {
+                return card.to_string();
+            }
+
+            let mut card = card.clone();
+            if options.merge_duplicate_types {
+                card.properties = merge_duplicate_types(&card.properties);
+            }
+            if options.canonical_order {
+                card.properties.sort_by_key(|p| {
+                    LEADING
+                        .iter()
+                        .position(|name| p.is(name))
+                        .unwrap_or(LEADING.len())
+                });
+            }
+            card.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Folds every property into the first one with the same group, name,
+/// value and non-`TYPE` parameters, keeping the position of the first and of
+/// its `TYPE` among its parameters.
+fn merge_duplicate_types<'a>(properties: &[Property<'a>]) -> Vec<Property<'a>> {
+    let mut merged: Vec<Property<'a>> = vec![];
+
+    for property in properties {
+        match merged.iter_mut().find(|m| same_but_types(m, property)) {
+            Some(existing) => {
+                let mut types: Vec<&str> = existing.types();
+                for t in property.types() {
+                    if !types.iter().any(|e| e.eq_ignore_ascii_case(t)) {
+                        types.push(t);
+                    }
+                }
+                let mut types = Some(types.join(",")).filter(|t| !t.is_empty());
+
+                existing.params.retain_mut(|(name, value)| {
+                    if !name.eq_ignore_ascii_case("TYPE") {
+                        return true;
+                    }
+                    match types.take() {
+                        Some(types) => {
+                            *value = Cow::Owned(types);
+                            true
+                        }
+                        None => false,
+                    }
+                });
+                if let Some(types) = types {
+                    existing.params.push(("TYPE".into(), Cow::Owned(types)));
+                }
+            }
+            None => merged.push(property.clone()),
+        }
+    }
+
+    merged
+}
+
+fn same_but_types(a: &Property<'_>, b: &Property<'_>) -> bool {
+    let other_params = |p: &Property<'_>| -> Vec<(String, String)> {
+        let mut params: Vec<(String, String)> = p
+            .params
+            .iter()
+            .filter(|(name, _)| !name.eq_ignore_ascii_case("TYPE"))
+            .map(|(name, value)| (name.to_ascii_uppercase(), value.to_string()))
+            .collect();
+        params.sort();
+        params
+    };
+
+    a.is(&b.name)
+        && a.group().map(str::to_ascii_uppercase) == b.group().map(str::to_ascii_uppercase)
+        && a.value == b.value
+        && other_params(a) == other_params(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_vcard, parse_vcards, TEST_STRING};
+
+    #[test]
+    fn round_trip() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+
+        assert_eq!(card.to_string(), TEST_STRING);
+    }
+
+    #[test]
+    fn folds_on_char_boundaries() {
+        let mut text = String::new();
+        write_folded(&mut text, &format!("NOTE:{}", "é".repeat(40))).unwrap();
+
+        let lines: Vec<&str> = text.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE));
+        assert_eq!(lines[1].len(), 11);
+    }
+
+    #[test]
+    fn skip_empty_cards() {
+        let text = format!("{}BEGIN:VCARD\r\nVERSION:3.0\r\nEND:VCARD\r\n", TEST_STRING);
+        let cards = parse_vcards(&text).unwrap();
+
+        assert!(!cards[0].is_empty());
+        assert!(cards[1].is_empty());
+        assert_eq!(serialize_vcards(&cards, &SerializeOptions::default()), text);
+
+        let options = SerializeOptions {
+            skip_empty: true,
+            ..SerializeOptions::default()
+        };
+        assert_eq!(serialize_vcards(&cards, &options), TEST_STRING);
+    }
+
+    #[test]
+    fn blank_line_between_cards() {
+        let text = ["Jane Doe", "John Doe", "Ann Lee"]
+            .map(|name| format!("BEGIN:VCARD\r\nVERSION:3.0\r\nFN:{}\r\nEND:VCARD\r\n", name))
+            .concat();
+        let cards = parse_vcards(&text).unwrap();
+
+        let options = SerializeOptions {
+            blank_line_between: true,
+            ..SerializeOptions::default()
+        };
+        let written = serialize_vcards(&cards, &options);
+        assert!(written.contains("END:VCARD\r\n\r\nBEGIN:VCARD\r\nVERSION:3.0\r\nFN:John Doe"));
+        assert!(written.ends_with("FN:Ann Lee\r\nEND:VCARD\r\n"));
+
+        let reparsed = parse_vcards(&written).unwrap();
+        assert_eq!(reparsed.len(), 3);
+        assert_eq!(reparsed, cards);
+    }
+
+    #[test]
+    fn merge_duplicate_types() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+EMAIL;TYPE=HOME:jane@example.com\r
+TEL;TYPE=CELL:+1 555 0100\r
+EMAIL;TYPE=WORK,home:jane@example.com\r
+EMAIL;TYPE=WORK:j.doe@example.com\r
+TEL;TYPE=VOICE;X-LABEL=Desk;TYPE=WORK:+1 555 0101\r
+TEL;X-LABEL=Desk;TYPE=FAX:+1 555 0101\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let options = SerializeOptions {
+            merge_duplicate_types: true,
+            ..SerializeOptions::default()
+        };
+        assert_eq!(
+            serialize_vcards(&[card], &options),
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+EMAIL;TYPE=HOME,WORK:jane@example.com\r
+TEL;TYPE=CELL:+1 555 0100\r
+EMAIL;TYPE=WORK:j.doe@example.com\r
+TEL;TYPE=VOICE,WORK,FAX;X-LABEL=Desk:+1 555 0101\r
+END:VCARD\r
+"
+        );
+    }
+
+    #[test]
+    fn write_to_vec() {
+        let input = TEST_STRING.repeat(2);
+        let cards = parse_vcards(&input).unwrap();
+
+        let mut out = Vec::new();
+        write_vcards(&cards, &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, input);
+        assert_eq!(parse_vcards(&text).unwrap(), cards);
+    }
+
+    #[test]
+    fn generic_properties_round_trip() {
+        let text = "BEGIN:VCARD\r
+VERSION:3.0\r
+PRODID:-//Example Corp.//Address Book 5.1//EN\r
+FN:Jane Doe\r
+MAILER:PigeonMail 2.1\r
+CLASS:CONFIDENTIAL\r
+END:VCARD\r
+";
+        let card = parse_vcard(text).unwrap();
+
+        assert_eq!(
+            card.get("PRODID").unwrap().value,
+            vec!["-//Example Corp.//Address Book 5.1//EN"]
+        );
+        assert_eq!(card.to_string(), text);
+    }
+
+    #[test]
+    fn dedup_types_on_write() {
+        let mut property = Property::new("EMAIL", "jane@example.com");
+        property.params = vec![
+            ("TYPE".into(), "HOME,WORK,home".into()),
+            ("TYPE".into(), "Work".into()),
+            ("TYPE".into(), "INTERNET,WORK".into()),
+        ];
+        assert_eq!(
+            property.to_string(),
+            "EMAIL;TYPE=HOME,WORK;TYPE=INTERNET:jane@example.com"
+        );
+
+        let mut card = parse_vcard(TEST_STRING).unwrap();
+        card.properties[3].params[0].1 = "CELL,HOME,CELL".into();
+        let options = SerializeOptions {
+            merge_duplicate_types: true,
+            ..SerializeOptions::default()
+        };
+        assert!(serialize_vcards(&[card], &options)
+            .contains("TEL;TYPE=CELL,HOME:+91 12342 12332\r\nTEL;TYPE=CELL:+1"));
+    }
+
+    #[test]
+    fn inline_media_as_data_uri_in_40() {
+        let mut photo = Property::new("PHOTO", "MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN");
+        photo.add_param("ENCODING", "b");
+        photo.add_param("TYPE", "JPEG");
+        let mut card = VCard::new(Version::V30, vec![photo]);
+
+        assert!(card
+            .to_string()
+            .contains("\r\nPHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN\r\n"));
+
+        card.set_version(Version::V40, false);
+        assert!(card
+            .to_string()
+            .contains("\r\nPHOTO:data:image/jpeg;base64,MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcN\r\n"));
+        assert_eq!(card.get("PHOTO").unwrap().params.len(), 2);
+    }
+
+    #[test]
+    fn deterministic_output() {
+        let text = "BEGIN:VCARD\r
+VERSION:3.0\r
+mobile:+1 555 0100\r
+EMAIL;TYPE=WORK;X-LABEL=Office;TYPE=HOME:jane@example.com\r
+N:Doe;Jane;;;\r
+EMAIL;TYPE=INTERNET:jane@example.com\r
+FN:Jane Doe\r
+END:VCARD\r
+";
+        let options = SerializeOptions {
+            merge_duplicate_types: true,
+            canonical_order: true,
+            ..SerializeOptions::default()
+        };
+        let export = || {
+            // A new map each time, so each is iterated in a different order.
+            let parse_options = crate::ParseOptions {
+                aliases: std::collections::HashMap::from([
+                    ("MOBILE".to_string(), "TEL;TYPE=CELL".to_string()),
+                    ("Mobile".to_string(), "TEL;TYPE=VOICE".to_string()),
+                    ("mObile".to_string(), "TEL;TYPE=HOME".to_string()),
+                ]),
+                ..Default::default()
+            };
+            let card = crate::parse_vcard_with(text, &parse_options).unwrap();
+            serialize_vcards(&[card], &options)
+        };
+
+        let first = export();
+        for _ in 0..20 {
+            assert_eq!(export().as_bytes(), first.as_bytes());
+        }
+    }
+
+    #[test]
+    fn canonical_order() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+EMAIL:jane@example.com\r
+TEL:+1 555 0100\r
+N:Doe;Jane;;;\r
+EMAIL:j.doe@example.com\r
+FN:Jane Doe\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let options = SerializeOptions {
+            canonical_order: true,
+            ..SerializeOptions::default()
+        };
+        assert_eq!(
+            serialize_vcards(&[card], &options),
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+N:Doe;Jane;;;\r
+EMAIL:jane@example.com\r
+TEL:+1 555 0100\r
+EMAIL:j.doe@example.com\r
+END:VCARD\r
+"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::escape::unescape_value;
+use crate::vcard::VCard;
+
+/// Something suspicious about a card that didn't stop it from parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// The `EMAIL` at `index` in [`VCard::properties`] isn't an address.
+    InvalidEmail { index: usize, value: String },
+    /// The card was cut off before `END:VCARD`, and lenient parsing kept
+    /// what came before.
+    UnexpectedEnd,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::InvalidEmail { index, value } => {
+                write!(f, "property {} is not an email address: {:?}", index, value)
+            }
+            Warning::UnexpectedEnd => write!(f, "card ends before END:VCARD"),
+        }
+    }
+}
+
+impl<'a> VCard<'a> {
+    /// Checks the values of the card, reporting each problem found.
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings: Vec<Warning> = self
+            .properties
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is("EMAIL"))
+            .filter_map(|(index, p)| {
+                let value = unescape_value(&p.raw_value()).into_owned();
+                (!is_email(value.trim())).then_some(Warning::InvalidEmail { index, value })
+            })
+            .collect();
+
+        if self.raw().is_some_and(|raw| !is_complete(raw)) {
+            warnings.push(Warning::UnexpectedEnd);
+        }
+        warnings
+    }
+
+    /// The domain of every `EMAIL` address on the card, in lower case, e.g.
+    /// `gmail.com`. Values that aren't addresses are skipped.
+    pub fn email_domains(&self) -> BTreeSet<String> {
+        self.get_all("EMAIL")
+            .into_iter()
+            .filter_map(|p| {
+                let value = unescape_value(&p.raw_value()).trim().to_string();
+                let (_, domain) = value.rsplit_once('@').filter(|_| is_email(&value))?;
+                Some(domain.to_lowercase())
+            })
+            .collect()
+    }
+}
+
+/// Whether the card's text runs through to its `END:VCARD` line.
+fn is_complete(raw: &str) -> bool {
+    let raw = raw.trim_end();
+    raw.get(raw.len().saturating_sub("END:VCARD".len())..)
+        .is_some_and(|end| end.eq_ignore_ascii_case("END:VCARD"))
+}
+
+/// A loose check of `local@domain`, permissive enough for the addresses
+/// real exports contain.
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.is_empty()
+        && !value.contains(char::is_whitespace)
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_vcard, TEST_STRING};
+
+    #[test]
+    fn invalid_email() {
+        assert!(parse_vcard(TEST_STRING).unwrap().validate().is_empty());
+
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+EMAIL:jane@example.com\r
+EMAIL;TYPE=WORK:jane at example.com\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.validate(),
+            vec![Warning::InvalidEmail {
+                index: 2,
+                value: "jane at example.com".to_string()
+            }]
+        );
+        for invalid in ["jane", "@example.com", "jane@", "jane@example..com"] {
+            assert!(!is_email(invalid), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn email_domains() {
+        assert_eq!(
+            parse_vcard(TEST_STRING).unwrap().email_domains(),
+            BTreeSet::from(["gmail.com".to_string()])
+        );
+
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+EMAIL:jane@Example.COM\r
+EMAIL:j.doe@example.com\r
+EMAIL:jane at work\r
+EMAIL:jane@mail.acme.test\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+        assert_eq!(
+            card.email_domains().into_iter().collect::<Vec<_>>(),
+            vec!["example.com", "mail.acme.test"]
+        );
+    }
+}
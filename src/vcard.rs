@@ -0,0 +1,1096 @@
+use std::borrow::Cow;
+
+use crate::escape::{escape_value, percent_decode, split_components, unescape_value};
+use crate::property::{Property, PropertyOwned};
+use crate::version::Version;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Name<'a> {
+    pub family_name: Cow<'a, str>,
+    pub given_name: Cow<'a, str>,
+    pub additional_name: Cow<'a, str>,
+    pub prefix: Cow<'a, str>,
+    pub suffix: Cow<'a, str>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VCard<'a> {
+    pub(crate) version: Version,
+    pub properties: Vec<Property<'a>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) raw: Option<Cow<'a, str>>,
+}
+
+/// The order to write a [`Name`]'s components in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameOrder {
+    /// `Dr. Jane Ann Doe Jr.`, as in most Western locales.
+    GivenFamily,
+    /// `Dr. Doe Jane Ann Jr.`, as in Chinese, Japanese, Korean or Hungarian.
+    FamilyGiven,
+}
+
+impl Name<'_> {
+    /// Every non-blank component in `order`, separated by single spaces.
+    pub fn formatted(&self, order: NameOrder) -> String {
+        let parts = match order {
+            NameOrder::GivenFamily => [
+                &self.prefix,
+                &self.given_name,
+                &self.additional_name,
+                &self.family_name,
+                &self.suffix,
+            ],
+            NameOrder::FamilyGiven => [
+                &self.prefix,
+                &self.family_name,
+                &self.given_name,
+                &self.additional_name,
+                &self.suffix,
+            ],
+        };
+
+        parts
+            .iter()
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The given and family names separated by a space, e.g. `Jane Doe`,
+    /// leaving out whichever is blank.
+    pub fn given_and_family(&self) -> String {
+        [&self.given_name, &self.family_name]
+            .iter()
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Splits and unescapes the value of an `N` property.
+    pub(crate) fn from_value(value: &str) -> Name<'static> {
+        let mut components = split_components(value)
+            .into_iter()
+            .map(|c| Cow::Owned(unescape_value(c).into_owned()));
+        let mut next = || components.next().unwrap_or_default();
+
+        Name {
+            family_name: next(),
+            given_name: next(),
+            additional_name: next(),
+            prefix: next(),
+            suffix: next(),
+        }
+    }
+
+    /// The value of an `N` property with these components, escaped.
+    pub(crate) fn to_value(&self) -> String {
+        [
+            &self.family_name,
+            &self.given_name,
+            &self.additional_name,
+            &self.prefix,
+            &self.suffix,
+        ]
+        .iter()
+        .map(|part| escape_value(part))
+        .collect::<Vec<_>>()
+        .join(";")
+    }
+}
+
+/// Cards are compared by content, regardless of the text they came from.
+impl PartialEq for VCard<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.properties == other.properties
+    }
+}
+
+impl Eq for VCard<'_> {}
+
+/// Collects properties into a 3.0 card. `full_name`, `name` and the other
+/// accessors read whatever `FN`, `N` and so on were collected.
+impl<'a> FromIterator<Property<'a>> for VCard<'a> {
+    fn from_iter<I: IntoIterator<Item = Property<'a>>>(properties: I) -> Self {
+        VCard::new(Version::default(), properties.into_iter().collect())
+    }
+}
+
+/// A [`VCard`] that owns all of its text, e.g. after editing.
+pub type VCardOwned = VCard<'static>;
+
+/// Properties a 3.0 card must have, empty or not.
+const REQUIRED: &[&str] = &["FN", "N", "VERSION"];
+
+impl<'a> VCard<'a> {
+    pub fn new(version: Version, properties: Vec<Property<'a>>) -> Self {
+        VCard {
+            version,
+            properties,
+            raw: None,
+        }
+    }
+
+    /// The unfolded text, from `BEGIN:VCARD` to the CRLF after `END:VCARD`,
+    /// that the card was parsed from. It isn't updated when the card is
+    /// edited, and cards that weren't parsed have none.
+    pub fn raw(&self) -> Option<&str> {
+        self.raw.as_deref()
+    }
+
+    /// The version declared by the card's `VERSION` line.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Changes the version the card declares. With `reescape`, the values of
+    /// text properties such as `NOTE` and `ADR` are also unescaped and
+    /// escaped again, so hand-written escapes like an unescaped comma that
+    /// one version tolerates are written the way the stricter one expects.
+    ///
+    /// Unlike [`convert_to`](VCard::convert_to), no properties or parameters
+    /// change.
+    pub fn set_version(&mut self, version: Version, reescape: bool) {
+        self.version = version;
+        if reescape {
+            for property in self.properties.iter_mut() {
+                crate::normalize::reescape(property);
+            }
+        }
+    }
+
+    /// Whether the card has nothing in it besides its `VERSION`.
+    pub fn is_empty(&self) -> bool {
+        self.properties.iter().all(|p| p.is("VERSION"))
+    }
+
+    /// The first property called `name`, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&Property<'a>> {
+        self.properties.iter().find(|p| p.is(name))
+    }
+
+    /// Every property called `name`, ignoring case, in card order.
+    pub fn get_all(&self, name: &str) -> Vec<&Property<'a>> {
+        self.properties.iter().filter(|p| p.is(name)).collect()
+    }
+
+    /// The first property called `name` in `group`, ignoring case for both.
+    pub fn get_grouped(&self, group: &str, name: &str) -> Option<&Property<'a>> {
+        self.properties
+            .iter()
+            .find(|p| p.in_group(group) && p.is(name))
+    }
+
+    /// Every property in `group`, ignoring case, in card order.
+    pub fn properties_in_group(&self, group: &str) -> Vec<&Property<'a>> {
+        self.properties
+            .iter()
+            .filter(|p| p.in_group(group))
+            .collect()
+    }
+
+    /// The [`canonical_name`](Property::canonical_name) of every property
+    /// on the card, each once, in the order they first appear. `VERSION`
+    /// isn't included.
+    pub fn property_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = vec![];
+        for property in self.properties.iter() {
+            let name = property.canonical_name();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// The [`types`](Property::types) of every property called `name`, in
+    /// card order, e.g. `[["CELL"], ["HOME", "VOICE"]]` for `TEL`.
+    pub fn types_for(&self, name: &str) -> Vec<Vec<String>> {
+        self.get_all(name)
+            .into_iter()
+            .map(|p| p.types().into_iter().map(str::to_string).collect())
+            .collect()
+    }
+
+    /// Every parameter called `name`, ignoring case, with the property that
+    /// carries it, in card order. A property with the parameter twice, such
+    /// as `TYPE=HOME;TYPE=WORK`, appears twice.
+    pub fn get_param_everywhere(&self, name: &str) -> Vec<(&Property<'a>, &str)> {
+        self.properties
+            .iter()
+            .flat_map(|p| {
+                p.params
+                    .iter()
+                    .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+                    .map(move |(_, value)| (p, value.as_ref()))
+            })
+            .collect()
+    }
+
+    /// Every [extension](Property::is_extension) property, in card order.
+    pub fn extensions(&self) -> Vec<&Property<'a>> {
+        self.properties
+            .iter()
+            .filter(|p| p.is_extension())
+            .collect()
+    }
+
+    /// The property called `name` whose `LANG` parameter is `lang`, or failing
+    /// that the first one without a `LANG`.
+    pub fn get_in_lang(&self, name: &str, lang: &str) -> Option<&Property<'a>> {
+        let mut unspecified = None;
+        for property in self.properties.iter().filter(|p| p.is(name)) {
+            match property.param("LANG") {
+                Some(l) if l.eq_ignore_ascii_case(lang) => return Some(property),
+                None if unspecified.is_none() => unspecified = Some(property),
+                _ => {}
+            }
+        }
+
+        unspecified
+    }
+
+    /// Every property whose `ALTID` parameter is `altid`: alternative
+    /// representations of the same value, such as a name written in two
+    /// scripts, usually told apart by their `LANG`.
+    pub fn alt_group(&self, altid: &str) -> Vec<&Property<'a>> {
+        self.properties
+            .iter()
+            .filter(|p| p.param("ALTID") == Some(altid))
+            .collect()
+    }
+
+    /// The contact's language from the first `LANG` property, e.g. `de`. Not
+    /// to be confused with the `LANG` parameter, see [`get_in_lang`].
+    ///
+    /// [`get_in_lang`]: VCard::get_in_lang
+    pub fn language(&self) -> Option<&str> {
+        self.get("LANG")
+            .and_then(|p| p.value.first())
+            .map(|v| v.as_ref())
+    }
+
+    /// The latitude and longitude from the first `GEO`, either a 2.1/3.0
+    /// `lat;lon` pair or a 4.0 `geo:` URI, whose percent escapes are decoded
+    /// first. `None` if there is no `GEO` or it doesn't hold two numbers.
+    pub fn geo(&self) -> Option<(f64, f64)> {
+        let raw = self.get("GEO")?.raw_value();
+        let uri = raw
+            .get(.."geo:".len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case("geo:"))
+            .map(|_| percent_decode(&raw["geo:".len()..]));
+
+        let (latitude, longitude) = match &uri {
+            Some(uri) => {
+                let coordinates = uri.split(';').next().unwrap_or_default();
+                let mut parts = coordinates.split(',');
+                (parts.next()?, parts.next()?)
+            }
+            None => raw.split_once(';')?,
+        };
+        Some((
+            latitude.trim().parse().ok()?,
+            longitude.trim().parse().ok()?,
+        ))
+    }
+
+    /// The URIs of every `CALURI`, where the contact's calendar can be found.
+    pub fn cal_uris(&self) -> Vec<&str> {
+        self.uris("CALURI")
+    }
+
+    /// The URIs of every `CALADRURI`, where scheduling requests should be
+    /// sent.
+    pub fn cal_adr_uris(&self) -> Vec<&str> {
+        self.uris("CALADRURI")
+    }
+
+    /// The URLs of every `FBURL`, where the contact's free/busy time can be
+    /// looked up.
+    pub fn fb_urls(&self) -> Vec<&str> {
+        self.uris("FBURL")
+    }
+
+    /// The URIs of every `CONTACT-URI`, such as a `mailto:` address or web
+    /// form, through which the contact can be reached.
+    pub fn contact_uris(&self) -> Vec<&str> {
+        self.uris("CONTACT-URI")
+    }
+
+    fn uris(&self, name: &str) -> Vec<&str> {
+        self.properties
+            .iter()
+            .filter(|p| p.is(name))
+            .filter_map(|p| p.value.first())
+            .map(|v| v.as_ref())
+            .collect()
+    }
+
+    /// The unescaped payload of every 4.0 `XML` property, with escaped
+    /// newlines restored.
+    pub fn xml(&self) -> Vec<String> {
+        self.get_all("XML")
+            .into_iter()
+            .map(|p| unescape_value(&p.raw_value()).into_owned())
+            .collect()
+    }
+
+    /// The unescaped value of the preferred `FN`. A card can have several,
+    /// for example in different languages, and all of them are in
+    /// `get_all("FN")`; this picks the one [`preferred`] does.
+    ///
+    /// [`preferred`]: VCard::preferred
+    pub fn full_name(&self) -> Option<String> {
+        self.preferred("FN")
+            .map(|p| unescape_value(&p.raw_value()).into_owned())
+    }
+
+    /// The property called `name` with the lowest 4.0 `PREF`, or failing that
+    /// the first with a 3.0 `TYPE=PREF`, or else simply the first.
+    pub fn preferred(&self, name: &str) -> Option<&Property<'a>> {
+        let all = self.get_all(name);
+        let pref = |p: &&Property<'a>| p.param("PREF").and_then(|v| v.parse::<u8>().ok());
+
+        all.iter()
+            .filter(|p| pref(p).is_some())
+            .min_by_key(|p| pref(p))
+            .or_else(|| {
+                all.iter()
+                    .find(|p| p.types().iter().any(|t| t.eq_ignore_ascii_case("PREF")))
+            })
+            .or(all.first())
+            .copied()
+    }
+
+    /// The value of the 3.0 `SORT-STRING`, the text to sort the contact by,
+    /// such as a reading of a name written in kanji.
+    pub fn sort_string(&self) -> Option<&str> {
+        self.get("SORT-STRING")
+            .and_then(|p| p.value.first())
+            .map(|value| value.as_ref())
+    }
+
+    /// The text to sort the contact by, from the first of these given:
+    ///
+    /// 1. the 4.0 `SORT-AS` parameter of `N` or `ORG`, its components
+    ///    separated by spaces
+    /// 2. [`sort_string`](VCard::sort_string)
+    /// 3. the family, given and additional names from `N`
+    pub fn sort_key(&self) -> Option<String> {
+        let sort_as = ["N", "ORG"]
+            .iter()
+            .filter_map(|name| self.get(name)?.param("SORT-AS"))
+            .find(|sort_as| !sort_as.trim().is_empty());
+        if let Some(sort_as) = sort_as {
+            return Some(
+                sort_as
+                    .split(',')
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+
+        if let Some(sort_string) = self.sort_string().filter(|s| !s.trim().is_empty()) {
+            return Some(unescape_value(sort_string).trim().to_string());
+        }
+
+        let name = self.name()?;
+        let key = [&name.family_name, &name.given_name, &name.additional_name]
+            .iter()
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(key).filter(|key| !key.is_empty())
+    }
+
+    /// The unescaped components of `N`. Missing trailing components are empty.
+    pub fn name(&self) -> Option<Name<'_>> {
+        self.get("N").map(|p| Name::from_value(&p.raw_value()))
+    }
+
+    /// The contact's `N` written out in `order`, or `None` if it has none or
+    /// every component is blank.
+    pub fn formatted_name(&self, order: NameOrder) -> Option<String> {
+        Some(self.name()?.formatted(order)).filter(|name| !name.is_empty())
+    }
+
+    /// The name to show for the contact, taken from the first of these that
+    /// isn't blank:
+    ///
+    /// 1. `FN`
+    /// 2. the given and family names from `N`
+    /// 3. the organization name, the first component of `ORG`
+    /// 4. the first `EMAIL`
+    pub fn display_name(&self) -> Option<String> {
+        let not_blank = |name: &String| !name.trim().is_empty();
+
+        if let Some(name) = self.full_name().filter(not_blank) {
+            return Some(name);
+        }
+
+        if let Some(name) = self.name().map(|name| name.given_and_family()) {
+            if !name.is_empty() {
+                return Some(name);
+            }
+        }
+
+        let organization = self.get("ORG").and_then(|p| {
+            let raw = p.raw_value();
+            let name = split_components(&raw).first().copied().unwrap_or_default();
+            Some(unescape_value(name).into_owned()).filter(not_blank)
+        });
+        if organization.is_some() {
+            return organization;
+        }
+
+        self.get("EMAIL")
+            .map(|p| unescape_value(&p.raw_value()).into_owned())
+            .filter(not_blank)
+    }
+
+    /// Gives the card the `FN` that 3.0 and 4.0 require if it has none, or
+    /// only a blank one, taking the [`display_name`](VCard::display_name)
+    /// from `N`, `ORG` or `EMAIL`. A new `FN` goes first. Returns whether the
+    /// card was changed, which it can't be when there is nothing to name it
+    /// by.
+    pub fn ensure_fn(&mut self) -> bool {
+        if self.full_name().is_some_and(|name| !name.trim().is_empty()) {
+            return false;
+        }
+        let Some(name) = self.display_name() else {
+            return false;
+        };
+
+        if self.get("FN").is_some() {
+            self.set("FN", &name);
+        } else {
+            let value = escape_value(&name).into_owned();
+            self.properties.insert(0, Property::new("FN", value));
+        }
+        true
+    }
+
+    /// Replaces every property called `name` with a single property holding
+    /// `value`, escaped. The new property takes the place of the first one
+    /// removed, or is appended if there was none.
+    pub fn set(&mut self, name: &str, value: &str) {
+        let position = self.properties.iter().position(|p| p.is(name));
+        self.remove(name);
+
+        let property = Property::new(name.to_string(), escape_value(value).into_owned());
+        match position {
+            Some(i) => self.properties.insert(i, property),
+            None => self.properties.push(property),
+        }
+    }
+
+    /// Removes every property called `name`, returning how many there were.
+    pub fn remove(&mut self, name: &str) -> usize {
+        let before = self.properties.len();
+        self.properties.retain(|p| !p.is(name));
+        before - self.properties.len()
+    }
+
+    /// Removes every property for which `predicate` holds, returning how
+    /// many there were, e.g. to strip photos and notes before sharing.
+    pub fn remove_property_if(
+        &mut self,
+        mut predicate: impl FnMut(&Property<'a>) -> bool,
+    ) -> usize {
+        let before = self.properties.len();
+        self.properties.retain(|p| !predicate(p));
+        before - self.properties.len()
+    }
+
+    /// Removes every property whose value components are all empty or
+    /// whitespace, such as `TEL:` or `NOTE: `, returning how many there
+    /// were. `FN` and `N` are kept, since 3.0 requires them even when blank.
+    pub fn clean(&mut self) -> usize {
+        let before = self.properties.len();
+        self.properties.retain(|p| {
+            REQUIRED.iter().any(|name| p.is(name)) || p.value.iter().any(|v| !v.trim().is_empty())
+        });
+        before - self.properties.len()
+    }
+
+    pub fn into_owned(self) -> VCardOwned {
+        VCard {
+            version: self.version,
+            properties: self
+                .properties
+                .into_iter()
+                .map(Property::into_owned)
+                .collect(),
+            raw: self.raw.map(|raw| Cow::Owned(raw.into_owned())),
+        }
+    }
+
+    /// Takes the card apart into its properties, in card order, for handling
+    /// them directly. Text that is already owned isn't copied. `VERSION` is
+    /// not among them, see [`version`](VCard::version).
+    pub fn into_properties(self) -> Vec<PropertyOwned> {
+        self.properties
+            .into_iter()
+            .map(Property::into_owned)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_vcard, TEST_STRING};
+
+    /// A card of the given version holding `lines` after `VERSION`.
+    fn vcard(version: &str, lines: &str) -> VCardOwned {
+        let text = format!("BEGIN:VCARD\r\nVERSION:{version}\r\n{lines}END:VCARD\r\n");
+        parse_vcard(&text).unwrap().into_owned()
+    }
+
+    #[test]
+    fn name_components() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+        let name = card.name().unwrap();
+
+        assert_eq!(card.full_name().as_deref(), Some("Hello Betty"));
+        assert_eq!(name.family_name, "Hello");
+        assert_eq!(name.given_name, "Betty");
+        assert_eq!(name.suffix, "");
+    }
+
+    #[test]
+    fn set_replaces_full_name() {
+        let mut card = parse_vcard(TEST_STRING).unwrap().into_owned();
+        card.properties.push(Property::new("fn", "Duplicate"));
+
+        card.set("fn", "Betty; Hello, Jr.");
+
+        assert_eq!(card.get_all("FN").len(), 1);
+        assert_eq!(card.properties[0].name, "fn");
+        assert_eq!(card.properties[0].value, vec!["Betty\\; Hello\\, Jr."]);
+        assert_eq!(card.full_name().as_deref(), Some("Betty; Hello, Jr."));
+    }
+
+    #[test]
+    fn remove_all_phones() {
+        let mut card = parse_vcard(TEST_STRING).unwrap().into_owned();
+
+        assert_eq!(card.remove("tel"), 2);
+        assert!(card.get("TEL").is_none());
+        assert_eq!(card.remove("TEL"), 0);
+    }
+
+    #[test]
+    fn calendar_uris() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+CALURI;PREF=1:http://cal.example.com/calA\r
+CALURI:ftp://ftp.example.com/calA.ics\r
+CALADRURI:mailto:janedoe@example.com\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.cal_uris(),
+            vec![
+                "http://cal.example.com/calA",
+                "ftp://ftp.example.com/calA.ics"
+            ]
+        );
+        assert_eq!(card.cal_adr_uris(), vec!["mailto:janedoe@example.com"]);
+    }
+
+    #[test]
+    fn free_busy_urls() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+FBURL;MEDIATYPE=text/calendar:ftp://example.com/busy/project-a.ifb\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(card.fb_urls(), vec!["ftp://example.com/busy/project-a.ifb"]);
+    }
+
+    #[test]
+    fn contact_uris() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Example Directory\r
+CONTACT-URI;PREF=1:mailto:contact@example.com?subject=Directory:%20Update\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.contact_uris(),
+            vec!["mailto:contact@example.com?subject=Directory:%20Update"]
+        );
+    }
+
+    #[test]
+    fn versions() {
+        for (number, version) in [
+            ("2.1", Version::V21),
+            ("3.0", Version::V30),
+            ("4.0", Version::V40),
+        ] {
+            let text = format!(
+                "BEGIN:VCARD\r\nVERSION:{}\r\nFN:Jane\r\nEND:VCARD\r\n",
+                number
+            );
+            let card = parse_vcard(&text).unwrap();
+
+            assert_eq!(card.version(), version);
+            assert_eq!(card.version().to_string(), number);
+        }
+    }
+
+    #[test]
+    fn property_in_language() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN;LANG=en:Jane Doe\r
+FN;LANG=fr:Jeanne Doe\r
+TITLE:Engineer\r
+TITLE;LANG=de:Ingenieurin\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.get_in_lang("FN", "fr").unwrap().value,
+            vec!["Jeanne Doe"]
+        );
+        assert_eq!(
+            card.get_in_lang("fn", "EN").unwrap().value,
+            vec!["Jane Doe"]
+        );
+        assert!(card.get_in_lang("FN", "de").is_none());
+        assert_eq!(
+            card.get_in_lang("TITLE", "en").unwrap().value,
+            vec!["Engineer"]
+        );
+        assert_eq!(
+            card.get_in_lang("TITLE", "de").unwrap().value,
+            vec!["Ingenieurin"]
+        );
+    }
+
+    #[test]
+    fn language() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN;LANG=en:Jane Doe\r
+LANG;PREF=1:de\r
+LANG;PREF=2:en\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(card.language(), Some("de"));
+        assert_eq!(parse_vcard(TEST_STRING).unwrap().language(), None);
+    }
+
+    #[test]
+    fn grouped_properties() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+TEL;TYPE=CELL:+1 555 0100\r
+item1.TEL:+1 555 0101\r
+item1.X-ABLabel:Work\r
+ITEM3.TEL:+1 555 0103\r
+item3.X-ABLabel:_$!<Other>!$_\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let phone = card.get_grouped("item3", "tel").unwrap();
+        assert_eq!(phone.group(), Some("ITEM3"));
+        assert_eq!(phone.value, vec!["+1 555 0103"]);
+        assert_eq!(
+            card.get_grouped("item1", "TEL").unwrap().value,
+            vec!["+1 555 0101"]
+        );
+        assert!(card.get_grouped("item2", "TEL").is_none());
+        assert_eq!(card.properties_in_group("Item1").len(), 2);
+        assert_eq!(card.get("TEL").unwrap().group(), None);
+    }
+
+    #[test]
+    fn set_version() {
+        let mut card = parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nNOTE:Lunch, then meetings\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+
+        card.set_version(Version::V40, false);
+        assert_eq!(card.version(), Version::V40);
+        assert!(card
+            .to_string()
+            .starts_with("BEGIN:VCARD\r\nVERSION:4.0\r\n"));
+        assert_eq!(
+            card.get("NOTE").unwrap().value,
+            vec!["Lunch, then meetings"]
+        );
+
+        card.set_version(Version::V40, true);
+        assert_eq!(
+            card.to_string(),
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nNOTE:Lunch\\, then meetings\r\nEND:VCARD\r\n"
+        );
+    }
+
+    #[test]
+    fn into_properties() {
+        let properties = parse_vcard(TEST_STRING).unwrap().into_properties();
+
+        assert_eq!(properties.len(), 9);
+        assert!(properties[0].is("FN"));
+        assert_eq!(properties[0].value, vec!["Hello Betty"]);
+        assert!(properties.iter().all(|p| !p.is("VERSION")));
+        assert_eq!(properties[8].list_values(), vec!["myContacts"]);
+    }
+
+    #[test]
+    fn property_names() {
+        let text = TEST_STRING.replace("TEL;", "tel;");
+        let card = parse_vcard(&text).unwrap();
+
+        assert_eq!(
+            card.property_names(),
+            vec![
+                "FN",
+                "N",
+                "EMAIL",
+                "TEL",
+                "ROLE",
+                "NOTE",
+                "PHOTO",
+                "CATEGORIES"
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_key() {
+        let sorted = vcard(
+            "3.0",
+            "FN:Jane Doe\r\nN:山田;太郎;;;\r\nSORT-STRING:Yamada Taro\r\n",
+        );
+        assert_eq!(sorted.sort_string(), Some("Yamada Taro"));
+        assert_eq!(sorted.sort_key().as_deref(), Some("Yamada Taro"));
+
+        let sort_as = vcard(
+            "3.0",
+            "FN:Jane Doe\r\nN;SORT-AS=\"Van Doe,Jane\":van Doe;Jane;;;\r\nSORT-STRING:Doe\r\n",
+        );
+        assert_eq!(sort_as.sort_key().as_deref(), Some("Van Doe Jane"));
+
+        let plain = vcard("3.0", "FN:Jane Doe\r\nN:Doe;Jane;Ann;Dr.;\r\n");
+        assert_eq!(plain.sort_string(), None);
+        assert_eq!(plain.sort_key().as_deref(), Some("Doe Jane Ann"));
+        assert_eq!(vcard("3.0", "FN:Jane Doe\r\n").sort_key(), None);
+    }
+
+    #[test]
+    fn types_for() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+
+        assert_eq!(card.types_for("tel"), vec![vec!["CELL"], vec!["CELL"]]);
+        assert_eq!(card.types_for("NOTE"), vec![Vec::<String>::new()]);
+        assert!(card.types_for("ADR").is_empty());
+    }
+
+    #[test]
+    fn param_everywhere() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+
+        let types: Vec<(String, &str)> = card
+            .get_param_everywhere("type")
+            .into_iter()
+            .map(|(p, value)| (p.canonical_name(), value))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("EMAIL".to_string(), "INTERNET"),
+                ("TEL".to_string(), "CELL"),
+                ("TEL".to_string(), "CELL"),
+            ]
+        );
+        assert!(card.get_param_everywhere("PREF").is_empty());
+    }
+
+    #[test]
+    fn vendor_extensions() {
+        let text = "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+X-ABLabel:Work\r
+vnd.ANDROID-NICKNAME:Janie\r
+VND-123-COLOR:blue\r
+item1.TEL:+1 555 0100\r
+END:VCARD\r
+";
+        let card = parse_vcard(text).unwrap();
+
+        let names: Vec<String> = card.extensions().iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "X-ABLabel:Work",
+                "vnd.ANDROID-NICKNAME:Janie",
+                "VND-123-COLOR:blue"
+            ]
+        );
+        assert!(!card.get("TEL").unwrap().is_extension());
+        assert_eq!(card.to_string(), text);
+    }
+
+    #[test]
+    fn geo() {
+        let encoded = vcard("4.0", "GEO:geo:37.386013%2C-122.082932;u=10\r\n");
+        assert_eq!(encoded.geo(), Some((37.386013, -122.082932)));
+        assert_eq!(
+            encoded.get("GEO").unwrap().value,
+            vec!["geo:37.386013%2C-122.082932;u=10"]
+        );
+        assert_eq!(
+            vcard("4.0", "GEO:geo:37.5,-122.5,12\r\n").geo(),
+            Some((37.5, -122.5))
+        );
+        assert_eq!(
+            vcard("4.0", "GEO:37.386013;-122.082932\r\n").geo(),
+            Some((37.386013, -122.082932))
+        );
+        assert_eq!(vcard("4.0", "GEO:geo:somewhere\r\n").geo(), None);
+    }
+
+    #[test]
+    fn ensure_fn() {
+        let mut card = parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;Jane;;;\r\nORG:Acme\\, Inc.\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+
+        assert!(card.ensure_fn());
+        assert_eq!(card.properties[0].to_string(), "FN:Jane Doe");
+        assert!(!card.ensure_fn());
+
+        card.remove("FN");
+        card.remove("N");
+        assert!(card.ensure_fn());
+        assert_eq!(card.properties[0].to_string(), "FN:Acme\\, Inc.");
+
+        let mut empty = parse_vcard("BEGIN:VCARD\r\nVERSION:3.0\r\nEND:VCARD\r\n").unwrap();
+        assert!(!empty.ensure_fn());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn display_name_fallbacks() {
+        let all =
+            "FN:Jane Doe\r\nN:Doe;Janet;;;\r\nORG:Acme\\, Inc.;Sales\r\nEMAIL:jane@example.com\r\n";
+
+        assert_eq!(
+            vcard("3.0", all).display_name().as_deref(),
+            Some("Jane Doe")
+        );
+        assert_eq!(
+            vcard("3.0", &all.replace("FN:Jane Doe", "FN: "))
+                .display_name()
+                .as_deref(),
+            Some("Janet Doe")
+        );
+        assert_eq!(
+            vcard("3.0", &all.replace("FN:Jane Doe\r\n", ""))
+                .display_name()
+                .as_deref(),
+            Some("Janet Doe")
+        );
+        assert_eq!(
+            vcard(
+                "3.0",
+                &all.replace("FN:Jane Doe\r\n", "").replace("Doe;Janet", ";")
+            )
+            .display_name()
+            .as_deref(),
+            Some("Acme, Inc.")
+        );
+        assert_eq!(
+            vcard("3.0", "EMAIL:jane@example.com\r\n")
+                .display_name()
+                .as_deref(),
+            Some("jane@example.com")
+        );
+        assert_eq!(vcard("3.0", "").display_name(), None);
+    }
+
+    #[test]
+    fn alternative_representations() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN;ALTID=1;LANG=ja:山田太郎\r
+FN;ALTID=1;LANG=en:Taro Yamada\r
+TITLE;ALTID=2:Engineer\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        let names: Vec<&str> = card
+            .alt_group("1")
+            .iter()
+            .map(|p| p.value[0].as_ref())
+            .collect();
+        assert_eq!(names, vec!["山田太郎", "Taro Yamada"]);
+        assert_eq!(
+            card.get_in_lang("FN", "en").unwrap().param("ALTID"),
+            Some("1")
+        );
+        assert_eq!(card.alt_group("2").len(), 1);
+        assert!(card.alt_group("3").is_empty());
+    }
+
+    #[test]
+    fn clean_empty_properties() {
+        let mut card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:3.0\r
+FN:Jane Doe\r
+N:;;;;\r
+TEL:\r
+TEL;TYPE=CELL:+1 555 0100\r
+NOTE: \r
+ADR;TYPE=HOME:;; ;;;;\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(card.clean(), 3);
+        let names: Vec<&str> = card.properties.iter().map(|p| p.name.as_ref()).collect();
+        assert_eq!(names, vec!["FN", "N", "TEL"]);
+        assert_eq!(card.get("TEL").unwrap().value, vec!["+1 555 0100"]);
+    }
+
+    #[test]
+    fn embedded_xml() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Jane Doe\r
+XML:<a xmlns=\"http://www.w3.org/1999/xhtml\"\\n    href=\"http://example.com\"\\, \\;>\\n  Jane\\n</a>\r
+END:VCARD\r
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.xml(),
+            vec![
+                "<a xmlns=\"http://www.w3.org/1999/xhtml\"\n    href=\"http://example.com\", ;>\n  Jane\n</a>"
+            ]
+        );
+    }
+
+    #[test]
+    fn clone_and_mutate() {
+        let card = parse_vcard(TEST_STRING).unwrap();
+        let mut copy = card.clone();
+        copy.set("FN", "Betty Hello");
+        copy.remove("TEL");
+
+        assert_eq!(card.full_name().as_deref(), Some("Hello Betty"));
+        assert_eq!(card.get_all("TEL").len(), 2);
+        assert_eq!(copy.full_name().as_deref(), Some("Betty Hello"));
+        assert_ne!(copy, card);
+
+        let owned: VCardOwned = card.clone().into_owned();
+        drop(card);
+        assert_eq!(owned.clone().name(), owned.name());
+    }
+
+    #[test]
+    fn several_full_names() {
+        let two = vcard("4.0", "FN;LANG=ja:山田太郎\r\nFN;LANG=en:Taro Yamada\r\n");
+        assert_eq!(two.get_all("FN").len(), 2);
+        assert_eq!(two.full_name().as_deref(), Some("山田太郎"));
+
+        let preferred = vcard("4.0", "FN;PREF=2:山田太郎\r\nFN;PREF=1:Taro Yamada\r\n");
+        assert_eq!(preferred.full_name().as_deref(), Some("Taro Yamada"));
+
+        let typed = vcard("4.0", "FN:山田太郎\r\nFN;TYPE=pref:Taro Yamada\r\n");
+        assert_eq!(typed.full_name().as_deref(), Some("Taro Yamada"));
+    }
+
+    #[test]
+    fn remove_matching_properties() {
+        let mut card = parse_vcard(TEST_STRING).unwrap();
+
+        let removed = card.remove_property_if(|p| p.is("PHOTO") || p.is("note"));
+        assert_eq!(removed, 2);
+        assert!(card.get("PHOTO").is_none());
+        assert!(card.get("NOTE").is_none());
+        assert_eq!(card.properties.len(), 7);
+    }
+
+    #[test]
+    fn formatted_names() {
+        let card = parse_vcard(
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:x\r\nN:Doe;Jane;Ann;Dr.;\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            card.formatted_name(NameOrder::GivenFamily).as_deref(),
+            Some("Dr. Jane Ann Doe")
+        );
+        assert_eq!(
+            card.formatted_name(NameOrder::FamilyGiven).as_deref(),
+            Some("Dr. Doe Jane Ann")
+        );
+
+        let blank = parse_vcard("BEGIN:VCARD\r\nVERSION:3.0\r\nN: ;;;;\r\nEND:VCARD\r\n").unwrap();
+        assert_eq!(blank.formatted_name(NameOrder::GivenFamily), None);
+    }
+
+    #[test]
+    fn collect_properties() {
+        let properties = vec![
+            Property::new("FN".to_string(), "Jane Doe".to_string()),
+            Property::new("N".to_string(), "Doe;Jane;;;".to_string()),
+            Property::new("EMAIL".to_string(), "jane@example.com".to_string()),
+        ];
+        let card: VCardOwned = properties.into_iter().collect();
+
+        assert_eq!(card.version(), Version::V30);
+        assert_eq!(card.full_name().as_deref(), Some("Jane Doe"));
+        assert_eq!(card.name().unwrap().given_name, "Jane");
+        assert_eq!(card.properties.len(), 3);
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Version {
+    V21,
+    #[default]
+    V30,
+    V40,
+}
+
+impl Version {
+    /// Looks up the version written as `number`, e.g. `"3.0"`.
+    pub fn from_number(number: &str) -> Option<Version> {
+        match number {
+            "2.1" => Some(Version::V21),
+            "3.0" => Some(Version::V30),
+            "4.0" => Some(Version::V40),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Version::V21 => "2.1",
+            Version::V30 => "3.0",
+            Version::V40 => "4.0",
+        }
+    }
+}
+
+/// Versions are written as their number, e.g. `"3.0"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let number = String::deserialize(deserializer)?;
+        Version::from_number(&number).ok_or_else(|| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&number), &"2.1, 3.0 or 4.0")
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
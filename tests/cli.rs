@@ -0,0 +1,16 @@
+#![cfg(feature = "gzip")]
+
+use std::process::Command;
+
+#[test]
+fn reads_gzipped_export() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vcard-parse"))
+        .arg("tests/fixtures/contacts.vcf.gz")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"Hello Betty\""));
+    assert_eq!(stdout.lines().count(), 9);
+}